@@ -2,11 +2,29 @@
 //! to be easily and cheaply sharable through cloning.
 
 use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt::{Debug, Display, Formatter, Result};
 use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
-use std::rc::Rc;
-use BaseList::{Cons, Nil};
+use std::iter::{self, FromIterator};
+use std::mem;
+use std::ops::Add;
+use std::result;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+pub use non_empty::NonEmptyList;
+pub use pointer::{ArcKind, PointerKind, RcKind};
+use pointer::Link;
+pub use shared::{InvalidNodeIndex, SharedLists};
+
+#[macro_use]
+mod macros;
+mod non_empty;
+mod pointer;
+mod shared;
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 
 /// An immutable cons list.
@@ -35,267 +53,1838 @@ use BaseList::{Cons, Nil};
 /// let list = cons(1, cons(2, cons(3, nil())));
 /// print_list(list.clone());
 /// ```
-pub struct List<A> {
-    rc: Rc<BaseList<A>>
+///
+/// By default, the nodes of a list are shared using `Rc`. A list whose
+/// nodes are shared using `Arc` instead, and which can therefore be sent
+/// to other threads, is available as [`ArcList`](type.ArcList.html).
+///
+/// The empty list does not allocate, so `nil` can be used in constants.
+///
+/// ```rust
+/// use nth_cons_list::{nil, List};
+///
+/// const EMPTY: List<i32> = nil();
+/// assert!(EMPTY.is_empty());
+/// ```
+pub struct List<A, P: PointerKind = RcKind> {
+    node: Option<Link<Node<A, P>, P>>
+}
+
+/// An immutable cons list whose nodes are shared using `Arc`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::thread;
+/// use nth_cons_list::ArcList;
+///
+/// let list = ArcList::cons(1, ArcList::cons(2, ArcList::nil()));
+/// let shared = list.clone();
+/// let sum = thread::spawn(move || shared.iter().sum::<i32>()).join().unwrap();
+/// assert_eq!(sum, 3);
+/// ```
+pub type ArcList<A> = List<A, ArcKind>;
+
+struct Node<A, P: PointerKind> {
+    head: A,
+    tail: List<A, P>,
+    /// The length of the list headed by this node.
+    len: usize,
+}
+
+impl<A: Clone, P: PointerKind> Clone for Node<A, P> {
+    fn clone(&self) -> Self {
+        Node { head: self.head.clone(), tail: self.tail.clone(), len: self.len }
+    }
+}
+
+impl<A, P: PointerKind> Clone for List<A, P> {
+    /// Clones the list by cloning a reference counted pointer to
+    /// the list's contents; this operation is very cheap.
+    fn clone(&self) -> Self {
+        List { node: self.node.clone() }
+    }
+}
+
+/// A borrowed view of the structure of a [list](struct.List.html),
+/// which can be used to match on it.
+///
+/// # Examples
+///
+/// ```rust
+/// use nth_cons_list::{cons, nil, List, ListRef};
+///
+/// fn sum(list: &List<i32>) -> i32 {
+///     match list.view() {
+///         ListRef::Cons(head, tail) => head + sum(tail),
+///         ListRef::Nil => 0,
+///     }
+/// }
+///
+/// assert_eq!(sum(&cons(1, cons(2, cons(3, nil())))), 6);
+/// ```
+pub enum ListRef<'a, A: 'a, P: 'a + PointerKind = RcKind> {
+    /// A non-empty list, consisting of its head and its tail.
+    Cons(&'a A, &'a List<A, P>),
+    /// The empty list.
+    Nil,
+}
+
+/// An error indicating that an operation which requires a non-empty
+/// list was performed on the empty list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmptyListError;
+
+impl Display for EmptyListError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "list is empty")
+    }
+}
+
+impl Error for EmptyListError {}
+
+/// Prepends the specified element at the head of the specified list.
+pub fn cons<A>(head: A, tail: List<A>) -> List<A> {
+    List::cons(head, tail)
 }
 
-enum BaseList<A> {
-    Cons(A, List<A>),
-    Nil
-}
+/// Returns the empty list; this does not allocate.
+pub const fn nil<A>() -> List<A> {
+    List::nil()
+}
+
+impl<A, P: PointerKind> List<A, P> {
+    /// Prepends the specified element at the head of the specified list.
+    pub fn cons(head: A, tail: List<A, P>) -> List<A, P> {
+        let len = tail.len() + 1;
+        List { node: Some(Link::new(Node { head, tail, len })) }
+    }
+
+    /// Returns the empty list; this does not allocate.
+    pub const fn nil() -> List<A, P> {
+        List { node: None }
+    }
+
+    /// Returns the first element of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is empty.
+    pub fn head(&self) -> &A {
+        match self.node {
+            Some(ref node) => &node.head,
+            None => panic!("`head` on empty List"),
+        }
+    }
+
+    /// Returns a list containing all elements except the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is empty.
+    pub fn tail(&self) -> List<A, P> {
+        match self.node {
+            Some(ref node) => node.tail.clone(),
+            None => panic!("`tail` on empty List"),
+        }
+    }
+
+    /// Returns the first element of the list, or `None` if
+    /// this list is empty.
+    pub fn head_opt(&self) -> Option<&A> {
+        self.node.as_ref().map(|node| &node.head)
+    }
+
+    /// Returns the first element of the list, or an error if
+    /// this list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nth_cons_list::{EmptyListError, List};
+    ///
+    /// fn first_two(list: &List<i32>) -> Result<(i32, i32), EmptyListError> {
+    ///     Ok((*list.try_head()?, *list.try_tail()?.try_head()?))
+    /// }
+    ///
+    /// assert_eq!(first_two(&List::cons(1, List::cons(2, List::nil()))), Ok((1, 2)));
+    /// assert_eq!(first_two(&List::cons(1, List::nil())), Err(EmptyListError));
+    /// ```
+    pub fn try_head(&self) -> result::Result<&A, EmptyListError> {
+        self.head_opt().ok_or(EmptyListError)
+    }
+
+    /// Returns a list containing all elements except the first,
+    /// or an error if this list is empty.
+    pub fn try_tail(&self) -> result::Result<List<A, P>, EmptyListError> {
+        self.tail_opt().ok_or(EmptyListError)
+    }
+
+    /// Returns a mutable reference to the first element of the list, or
+    /// `None` if this list is empty.
+    ///
+    /// If the first node of this list is shared with another list, it is
+    /// first replaced with a copy, so that the other list is unaffected;
+    /// the rest of this list remains shared.
+    pub fn head_mut(&mut self) -> Option<&mut A> where A: Clone {
+        self.node.as_mut().map(|ptr| &mut Link::make_mut(ptr).head)
+    }
+
+    /// Returns a mutable reference to the first element of the list, or
+    /// `None` if this list is empty or its first node is shared with
+    /// another list.
+    pub fn try_head_mut(&mut self) -> Option<&mut A> {
+        self.node.as_mut().and_then(Link::get_mut).map(|node| &mut node.head)
+    }
+
+    /// Returns the first element of the list, or `None` if
+    /// this list is empty.
+    ///
+    /// This is the same as [`head_opt`](#method.head_opt), for use when
+    /// treating the list as a stack.
+    pub fn peek(&self) -> Option<&A> {
+        self.head_opt()
+    }
+
+    /// Prepends the specified element at the head of this list.
+    pub fn push_front(&mut self, value: A) {
+        let tail = mem::take(self);
+        *self = List::cons(value, tail);
+    }
+
+    /// Removes the first element from this list and returns it, or
+    /// returns `None` if this list is empty.
+    ///
+    /// The element is moved out of the first node if it is uniquely
+    /// owned by this list, and cloned otherwise.
+    pub fn pop_front(&mut self) -> Option<A> where A: Clone {
+        let ptr = self.node.take()?;
+        match Link::try_unwrap(ptr) {
+            Ok(Node { head, tail, .. }) => {
+                *self = tail;
+                Some(head)
+            }
+            Err(ptr) => {
+                *self = ptr.tail.clone();
+                Some(ptr.head.clone())
+            }
+        }
+    }
+
+    /// Returns a list containing all elements except the first,
+    /// or `None` if this list is empty.
+    pub fn tail_opt(&self) -> Option<List<A, P>> {
+        self.node.as_ref().map(|node| node.tail.clone())
+    }
+
+    /// Returns the first element of the list and a reference to the
+    /// list containing all elements except the first, or `None` if this
+    /// list is empty.
+    pub fn uncons(&self) -> Option<(&A, &List<A, P>)> {
+        self.node.as_ref().map(|node| (&node.head, &node.tail))
+    }
+
+    /// Returns a view of this list which can be used to match on
+    /// its structure.
+    pub fn view(&self) -> ListRef<'_, A, P> {
+        match self.node {
+            Some(ref node) => ListRef::Cons(&node.head, &node.tail),
+            None => ListRef::Nil,
+        }
+    }
+
+    /// Tests whether this list and the specified list are the same list,
+    /// that is, whether they share their first node or are both empty.
+    ///
+    /// Lists which are the same are always equal, but equal lists are not
+    /// necessarily the same.
+    pub fn ptr_eq(&self, other: &List<A, P>) -> bool {
+        match (self.node.as_ref(), other.node.as_ref()) {
+            (Some(a), Some(b)) => Link::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Tests whether this list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.node.is_none()
+    }
+
+    /// Returns the length of this list.
+    ///
+    /// The length is cached in each node, so this operation is `O(1)`.
+    pub fn len(&self) -> usize {
+        self.node.as_ref().map_or(0, |node| node.len)
+    }
+
+    /// Returns an iterator over the elements of this list.
+    pub fn iter(&self) -> Iter<'_, A, P> {
+        Iter { list: self }
+    }
+
+    /// Returns the element at the specified index, or `None` if the
+    /// index is out of range.
+    pub fn get(&self, index: usize) -> Option<&A> {
+        self.nth_tail(index).and_then(List::head_opt)
+    }
+
+    /// Returns the last element of the list, or `None` if this
+    /// list is empty.
+    pub fn last(&self) -> Option<&A> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns a reference to the list containing all elements except
+    /// the first `n`, or `None` if this list has fewer than `n` elements.
+    pub fn nth_tail(&self, n: usize) -> Option<&List<A, P>> {
+        if n > self.len() {
+            return None;
+        }
+
+        let mut rest = self;
+        for _ in 0..n {
+            rest = &rest.node.as_ref()?.tail;
+        }
+        Some(rest)
+    }
+
+    /// Returns a list containing the first `n` elements of this list,
+    /// or all of them if it has fewer than `n` elements.
+    ///
+    /// If this list has no more than `n` elements, the returned list is
+    /// a clone of it.
+    pub fn take(&self, n: usize) -> List<A, P> where A: Clone {
+        if n >= self.len() {
+            self.clone()
+        } else {
+            List::prepend_iter(self.iter().take(n).cloned(), List::nil())
+        }
+    }
+
+    /// Returns a list containing all elements except the first `n`, or
+    /// the empty list if this list has fewer than `n` elements.
+    ///
+    /// The returned list is shared with this one rather than copied.
+    pub fn drop(&self, n: usize) -> List<A, P> {
+        self.nth_tail(n).cloned().unwrap_or_default()
+    }
+
+    /// Splits this list into a list containing its first `n` elements
+    /// and a list containing the rest, which is shared with this one.
+    pub fn split_at(&self, n: usize) -> (List<A, P>, List<A, P>) where A: Clone {
+        (self.take(n), self.drop(n))
+    }
+
+    /// Returns a list containing the longest prefix of this list whose
+    /// elements satisfy the specified predicate.
+    ///
+    /// If every element satisfies the predicate, the returned list is a
+    /// clone of this one.
+    pub fn take_while<F: FnMut(&A) -> bool>(&self, mut pred: F) -> List<A, P> where A: Clone {
+        let mut count = 0;
+        let mut rest = self;
+        while let Some(ref node) = rest.node {
+            if !pred(&node.head) {
+                return List::prepend_iter(self.iter().take(count).cloned(), List::nil());
+            }
+            count += 1;
+            rest = &node.tail;
+        }
+        self.clone()
+    }
+
+    /// Returns the list remaining after removing the longest prefix of
+    /// this list whose elements satisfy the specified predicate.
+    ///
+    /// The returned list is shared with this one rather than copied.
+    pub fn drop_while<F: FnMut(&A) -> bool>(&self, mut pred: F) -> List<A, P> {
+        let mut rest = self;
+        while let Some(ref node) = rest.node {
+            if !pred(&node.head) {
+                break;
+            }
+            rest = &node.tail;
+        }
+        rest.clone()
+    }
+
+    /// Returns a list with the element at the specified index replaced
+    /// by the specified value.
+    ///
+    /// Only the elements preceding the index are copied; the elements
+    /// following it are shared with this list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn updated(&self, index: usize, value: A) -> List<A, P> where A: Clone {
+        let len = self.len();
+        assert!(index < len, "update index (is {}) should be < len (is {})", index, len);
+        self.replace_range(index, 1, Some(value))
+    }
+
+    /// Returns a list with the specified value inserted at the
+    /// specified index.
+    ///
+    /// Only the elements preceding the index are copied; the elements
+    /// following it are shared with this list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the length of this list.
+    pub fn insert_at(&self, index: usize, value: A) -> List<A, P> where A: Clone {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {}) should be <= len (is {})", index, len);
+        self.replace_range(index, 0, Some(value))
+    }
+
+    /// Returns a list with the element at the specified index removed.
+    ///
+    /// Only the elements preceding the index are copied; the elements
+    /// following it are shared with this list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn remove_at(&self, index: usize) -> List<A, P> where A: Clone {
+        let len = self.len();
+        assert!(index < len, "removal index (is {}) should be < len (is {})", index, len);
+        self.replace_range(index, 1, None)
+    }
+
+    /// Returns a list with the first element which satisfies the
+    /// specified predicate removed.
+    ///
+    /// Only the elements preceding it are copied; the elements following
+    /// it are shared with this list. If no element satisfies the
+    /// predicate, the returned list is a clone of this one.
+    pub fn remove_first<F: FnMut(&A) -> bool>(&self, pred: F) -> List<A, P> where A: Clone {
+        match self.position(pred) {
+            Some(index) => self.replace_range(index, 1, None),
+            None => self.clone(),
+        }
+    }
+
+    /// Returns a list with the `count` elements starting at the specified
+    /// index replaced by `value`, if any, copying only the elements
+    /// preceding the index.
+    fn replace_range(&self, index: usize, count: usize, value: Option<A>) -> List<A, P> where A: Clone {
+        let tail = self.drop(index + count);
+        List::prepend_iter(self.iter().take(index).cloned().chain(value), tail)
+    }
+
+    /// Tests whether this list contains the specified element.
+    pub fn contains(&self, elem: &A) -> bool where A: PartialEq {
+        self.iter().any(|e| e == elem)
+    }
+
+    /// Returns the first element which satisfies the specified predicate,
+    /// or `None` if there is no such element.
+    pub fn find<F: FnMut(&A) -> bool>(&self, mut pred: F) -> Option<&A> {
+        self.iter().find(|elem| pred(elem))
+    }
+
+    /// Returns the index of the first element which satisfies the
+    /// specified predicate, or `None` if there is no such element.
+    pub fn position<F: FnMut(&A) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Tests whether any element satisfies the specified predicate.
+    pub fn any<F: FnMut(&A) -> bool>(&self, pred: F) -> bool {
+        self.iter().any(pred)
+    }
+
+    /// Tests whether every element satisfies the specified predicate.
+    pub fn all<F: FnMut(&A) -> bool>(&self, pred: F) -> bool {
+        self.iter().all(pred)
+    }
+
+    /// Returns the number of elements which satisfy the
+    /// specified predicate.
+    pub fn count_by<F: FnMut(&A) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().filter(|elem| pred(elem)).count()
+    }
+
+    /// Returns the list starting at the first element which satisfies the
+    /// specified predicate, or the empty list if there is no
+    /// such element.
+    ///
+    /// The returned list is shared with this one rather than copied.
+    pub fn find_tail<F: FnMut(&A) -> bool>(&self, mut pred: F) -> List<A, P> {
+        self.drop_while(|elem| !pred(elem))
+    }
+
+    /// Returns an iterator over every suffix of this list, starting with
+    /// this list itself and ending with the empty list.
+    pub fn tails(&self) -> Tails<'_, A, P> {
+        Tails { list: Some(self) }
+    }
+
+    /// Returns a list with the elements in reverse order.
+    pub fn reverse(&self) -> List<A, P> where A: Clone {
+        let mut list = List::nil();
+        let mut rest = self;
+
+        while let Some(ref node) = rest.node {
+            list = List::cons(node.head.clone(), list);
+            rest = &node.tail;
+        }
+
+        list
+    }
+
+    /// Returns a list containing the results of applying the specified
+    /// function to each element of this list.
+    pub fn map<B, F: FnMut(&A) -> B>(&self, f: F) -> List<B, P> {
+        List::prepend_iter(self.iter().map(f), List::nil())
+    }
+
+    /// Returns a list containing only the elements of this list which
+    /// satisfy the specified predicate.
+    ///
+    /// The elements following the last element which does not satisfy
+    /// the predicate are shared with this list rather than copied; if
+    /// every element satisfies it, the returned list is a clone of
+    /// this one.
+    pub fn filter<F: FnMut(&A) -> bool>(&self, pred: F) -> List<A, P> where A: Clone {
+        self.retain_shared(pred)
+    }
+
+    /// Returns a list containing the values of applying the specified
+    /// function to each element of this list, for which it
+    /// returns `Some`.
+    pub fn filter_map<B, F: FnMut(&A) -> Option<B>>(&self, f: F) -> List<B, P> {
+        List::prepend_iter(self.iter().filter_map(f), List::nil())
+    }
+
+    /// Returns a list containing the elements of the results of applying
+    /// the specified function to each element of this list.
+    pub fn flat_map<B, I, F>(&self, f: F) -> List<B, P>
+        where I: IntoIterator<Item=B>, F: FnMut(&A) -> I {
+        List::prepend_iter(self.iter().flat_map(f), List::nil())
+    }
+
+    /// Folds the elements of this list from first to last, starting with
+    /// the specified initial value.
+    pub fn fold_left<B, F: FnMut(B, &A) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Folds the elements of this list from last to first, starting with
+    /// the specified initial value.
+    ///
+    /// This does not recurse, so it can be used on lists of any length.
+    pub fn fold_right<B, F: FnMut(&A, B) -> B>(&self, init: B, mut f: F) -> B {
+        let elems: Vec<&A> = self.iter().collect();
+        elems.into_iter().rev().fold(init, |acc, elem| f(elem, acc))
+    }
+
+    /// Reduces the elements of this list from first to last using the
+    /// specified function, or returns `None` if this list is empty.
+    pub fn reduce_left<F: FnMut(A, &A) -> A>(&self, f: F) -> Option<A> where A: Clone {
+        self.uncons().map(|(head, tail)| tail.fold_left(head.clone(), f))
+    }
+
+    /// Reduces the elements of this list from last to first using the
+    /// specified function, or returns `None` if this list is empty.
+    pub fn reduce_right<F: FnMut(&A, A) -> A>(&self, mut f: F) -> Option<A> where A: Clone {
+        let mut elems: Vec<&A> = self.iter().collect();
+        let last = elems.pop()?.clone();
+        Some(elems.into_iter().rev().fold(last, |acc, elem| f(elem, acc)))
+    }
+
+    /// Returns a list containing the specified initial value followed by
+    /// each intermediate result of folding the elements of this list from
+    /// first to last.
+    pub fn scan_left<B: Clone, F: FnMut(&B, &A) -> B>(&self, init: B, mut f: F) -> List<B, P> {
+        let results = self.iter().scan(init.clone(), |acc, elem| {
+            *acc = f(acc, elem);
+            Some(acc.clone())
+        });
+        List::prepend_iter(iter::once(init).chain(results), List::nil())
+    }
+
+    /// Returns a list containing each intermediate result of folding the
+    /// elements of this list from last to first, followed by the
+    /// specified initial value.
+    ///
+    /// This does not recurse, so it can be used on lists of any length.
+    pub fn scan_right<B, F: FnMut(&A, &B) -> B>(&self, init: B, mut f: F) -> List<B, P> {
+        let elems: Vec<&A> = self.iter().collect();
+        let mut list = List::cons(init, List::nil());
+        for elem in elems.into_iter().rev() {
+            let result = f(elem, list.head());
+            list = List::cons(result, list);
+        }
+        list
+    }
+
+    /// Returns a list containing the elements of this list followed by
+    /// the elements of the specified list.
+    ///
+    /// Only the elements of this list are copied; the specified list is
+    /// shared as the tail of the returned list.
+    pub fn append(&self, other: &List<A, P>) -> List<A, P> where A: Clone {
+        List::prepend_iter(self.iter().cloned(), other.clone())
+    }
+
+    /// Returns a list of pairs of corresponding elements of this list and
+    /// the specified list, as long as the shorter of the two.
+    pub fn zip<B: Clone>(&self, other: &List<B, P>) -> List<(A, B), P> where A: Clone {
+        self.zip_with(other, |a, b| (a.clone(), b.clone()))
+    }
+
+    /// Returns a list containing the results of applying the specified
+    /// function to corresponding elements of this list and the specified
+    /// list, as long as the shorter of the two.
+    pub fn zip_with<B, C, F: FnMut(&A, &B) -> C>(&self, other: &List<B, P>, mut f: F) -> List<C, P> {
+        List::prepend_iter(self.iter().zip(other.iter()).map(|(a, b)| f(a, b)), List::nil())
+    }
+
+    /// Returns a list of pairs of corresponding elements of this list and
+    /// the specified list, as long as the longer of the two; elements
+    /// missing from the shorter list are `None`.
+    pub fn zip_longest<B: Clone>(&self, other: &List<B, P>) -> List<(Option<A>, Option<B>), P>
+        where A: Clone {
+        let mut left = self.iter();
+        let mut right = other.iter();
+        let pairs = iter::from_fn(|| match (left.next(), right.next()) {
+            (None, None) => None,
+            (a, b) => Some((a.cloned(), b.cloned())),
+        });
+        List::prepend_iter(pairs, List::nil())
+    }
+
+    /// Splits this list into a list of the elements which satisfy the
+    /// specified predicate and a list of those which do not, each in
+    /// their original order.
+    pub fn partition<F>(&self, mut pred: F) -> (List<A, P>, List<A, P>)
+        where A: Clone, F: FnMut(&A) -> bool {
+        let (matching, rest): (Vec<&A>, Vec<&A>) = self.iter().partition(|elem| pred(elem));
+        (List::prepend_iter(matching.into_iter().cloned(), List::nil()),
+         List::prepend_iter(rest.into_iter().cloned(), List::nil()))
+    }
+
+    /// Splits this list into the longest prefix whose elements satisfy
+    /// the specified predicate and the list remaining after it, which is
+    /// shared with this one.
+    pub fn span<F>(&self, mut pred: F) -> (List<A, P>, List<A, P>)
+        where A: Clone, F: FnMut(&A) -> bool {
+        let mut count = 0;
+        let mut rest = self;
+        while let Some(ref node) = rest.node {
+            if !pred(&node.head) {
+                break;
+            }
+            count += 1;
+            rest = &node.tail;
+        }
+        (self.take(count), rest.clone())
+    }
+
+    /// Splits this list into the longest prefix whose elements do not
+    /// satisfy the specified predicate and the list remaining after it,
+    /// which is shared with this one.
+    pub fn break_at<F>(&self, mut pred: F) -> (List<A, P>, List<A, P>)
+        where A: Clone, F: FnMut(&A) -> bool {
+        self.span(|elem| !pred(elem))
+    }
+
+    /// Splits this list into runs of consecutive elements, where the
+    /// specified function returns `true` for each element and the
+    /// element preceding it in the same run.
+    ///
+    /// The last run is shared with this list rather than copied.
+    pub fn group_by<F>(&self, mut same: F) -> List<List<A, P>, P>
+        where A: Clone, F: FnMut(&A, &A) -> bool {
+        let mut groups = Vec::new();
+        let mut start = self;
+        let mut len = 0;
+        let mut rest = self;
+
+        while let Some(ref node) = rest.node {
+            if let Some(ref next) = node.tail.node {
+                len += 1;
+                if !same(&node.head, &next.head) {
+                    groups.push((start, len));
+                    start = &node.tail;
+                    len = 0;
+                }
+            } else {
+                groups.push((start, len + 1));
+            }
+            rest = &node.tail;
+        }
+
+        List::prepend_iter(groups.into_iter().map(|(start, len)| start.take(len)), List::nil())
+    }
+
+    /// Splits this list into lists of `size` consecutive elements; the
+    /// last list has fewer elements if the length of this list is not a
+    /// multiple of `size`.
+    ///
+    /// The last list is shared with this list rather than copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(&self, size: usize) -> List<List<A, P>, P> where A: Clone {
+        assert!(size != 0, "chunk size must be non-zero");
+        let count = self.len().div_ceil(size);
+        let chunks = self.tails().step_by(size).take(count);
+        List::prepend_iter(chunks.map(|suffix| suffix.take(size)), List::nil())
+    }
+
+    /// Returns a list of every run of `size` consecutive elements of this
+    /// list, in order; if this list has fewer than `size` elements, the
+    /// returned list is empty.
+    ///
+    /// The last window is shared with this list rather than copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn sliding(&self, size: usize) -> List<List<A, P>, P> where A: Clone {
+        assert!(size != 0, "window size must be non-zero");
+        let count = (self.len() + 1).saturating_sub(size);
+        List::prepend_iter(self.tails().take(count).map(|suffix| suffix.take(size)), List::nil())
+    }
+
+    /// Returns a list with the elements of this list sorted.
+    ///
+    /// The sort is stable, so equal elements keep their relative order.
+    pub fn sort(&self) -> List<A, P> where A: Ord + Clone {
+        self.sort_by(Ord::cmp)
+    }
+
+    /// Returns a list with the elements of this list sorted using the
+    /// specified comparison function.
+    ///
+    /// The sort is stable, so equal elements keep their relative order.
+    pub fn sort_by<F: FnMut(&A, &A) -> Ordering>(&self, mut compare: F) -> List<A, P> where A: Clone {
+        let mut elems: Vec<&A> = self.iter().collect();
+        elems.sort_by(|a, b| compare(a, b));
+        List::prepend_iter(elems.into_iter().cloned(), List::nil())
+    }
+
+    /// Returns a list with the elements of this list sorted by the keys
+    /// extracted by the specified function.
+    ///
+    /// The sort is stable, so elements with equal keys keep their
+    /// relative order.
+    pub fn sort_by_key<K: Ord, F: FnMut(&A) -> K>(&self, mut f: F) -> List<A, P> where A: Clone {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Merges this sorted list with the specified sorted list, returning
+    /// a sorted list containing the elements of both.
+    ///
+    /// Equal elements of this list are placed before those of the
+    /// specified list. Once the elements of either list are exhausted,
+    /// the rest of the other list is shared rather than copied.
+    pub fn merge(&self, other: &List<A, P>) -> List<A, P> where A: Ord + Clone {
+        self.merge_by(other, Ord::cmp)
+    }
+
+    /// Merges this list with the specified list, both sorted according
+    /// to the specified comparison function, returning a sorted list
+    /// containing the elements of both.
+    ///
+    /// Equal elements of this list are placed before those of the
+    /// specified list. Once the elements of either list are exhausted,
+    /// the rest of the other list is shared rather than copied.
+    pub fn merge_by<F>(&self, other: &List<A, P>, mut compare: F) -> List<A, P>
+        where A: Clone, F: FnMut(&A, &A) -> Ordering {
+        let mut merged = Vec::new();
+        let mut left = self;
+        let mut right = other;
+
+        while let (Some((a, a_tail)), Some((b, b_tail))) = (left.uncons(), right.uncons()) {
+            if compare(a, b) == Ordering::Greater {
+                merged.push(b);
+                right = b_tail;
+            } else {
+                merged.push(a);
+                left = a_tail;
+            }
+        }
+
+        let rest = if left.is_empty() { right } else { left };
+        List::prepend_iter(merged.into_iter().cloned(), rest.clone())
+    }
+
+    /// Returns a list with consecutive equal elements of this list
+    /// reduced to one.
+    ///
+    /// The elements following the last one removed are shared with this
+    /// list rather than copied.
+    pub fn dedup(&self) -> List<A, P> where A: PartialEq + Clone {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Returns a list with consecutive elements of this list for which the
+    /// specified function returns `true` reduced to the first of them.
+    ///
+    /// The function is passed each element and the last element which was
+    /// kept before it. The elements following the last one removed are
+    /// shared with this list rather than copied.
+    pub fn dedup_by<F: FnMut(&A, &A) -> bool>(&self, mut same: F) -> List<A, P> where A: Clone {
+        let mut prev = None;
+        self.retain_shared(|elem| {
+            let keep = prev.is_none_or(|prev| !same(elem, prev));
+            if keep {
+                prev = Some(elem);
+            }
+            keep
+        })
+    }
+
+    /// Creates a new list from a `DoubleEndedIterator`.
+    pub fn from_double_ended_iter<I: DoubleEndedIterator<Item=A>>(iter: I) -> List<A, P> {
+        let mut list = List::nil();
+        for elem in iter.rev() {
+            list = List::cons(elem, list);
+        }
+        list
+    }
+
+    /// Creates a new list containing the elements of the specified
+    /// iterator followed by the specified tail, which is shared rather
+    /// than copied.
+    ///
+    /// The list is built front to back, by writing each new node into the
+    /// tail of the previous one while it is still uniquely owned, so no
+    /// intermediate buffer is needed.
+    fn prepend_iter<I: IntoIterator<Item=A>>(iter: I, tail: List<A, P>) -> List<A, P> {
+        let mut list = List::nil();
+        let mut count = 0;
+        let mut cursor = &mut list;
+        for head in iter {
+            *cursor = List { node: Some(Link::new(Node { head, tail: List::nil(), len: 0 })) };
+            cursor = &mut cursor.unique_node_mut().tail;
+            count += 1;
+        }
+
+        // The lengths of the new nodes are only known once the
+        // iterator is exhausted
+        let mut len = count + tail.len();
+        *cursor = tail;
+        let mut cursor = &mut list;
+        for _ in 0..count {
+            let node = cursor.unique_node_mut();
+            node.len = len;
+            len -= 1;
+            cursor = &mut node.tail;
+        }
+
+        list
+    }
+
+    /// Returns a list containing only the elements of this list for which
+    /// `keep` returns `true`, sharing the elements following the last one
+    /// for which it returns `false`.
+    fn retain_shared<'a, F: FnMut(&'a A) -> bool>(&'a self, mut keep: F) -> List<A, P> where A: Clone {
+        let mut kept = Vec::new();
+        let mut prefix_len = 0;
+        let mut suffix = self;
+        let mut rest = self;
+
+        while let Some(ref node) = rest.node {
+            if keep(&node.head) {
+                kept.push(&node.head);
+            } else {
+                prefix_len = kept.len();
+                suffix = &node.tail;
+            }
+            rest = &node.tail;
+        }
+
+        List::prepend_iter(kept[..prefix_len].iter().map(|&elem| elem.clone()), suffix.clone())
+    }
+
+    /// Returns a mutable reference to the first node of a list which was
+    /// just built, and is therefore known to be uniquely owned.
+    fn unique_node_mut(&mut self) -> &mut Node<A, P> {
+        self.node.as_mut()
+            .and_then(Link::get_mut)
+            .expect("node is uniquely owned")
+    }
+}
+
+impl<A: Clone, P: PointerKind> List<List<A, P>, P> {
+    /// Returns a list containing the elements of each list in this list,
+    /// in order.
+    ///
+    /// The elements of every list but the last are copied; the last list
+    /// is shared as the tail of the returned list.
+    pub fn concat(&self) -> List<A, P> {
+        let mut lists: Vec<&List<A, P>> = self.iter().collect();
+        match lists.pop() {
+            Some(last) => {
+                let init = lists.into_iter().flat_map(|list| list.iter().cloned());
+                List::prepend_iter(init, last.clone())
+            }
+            None => List::nil(),
+        }
+    }
+}
+
+impl<A: Clone, B: Clone, P: PointerKind> List<(A, B), P> {
+    /// Returns a list of the first elements of the pairs in this list,
+    /// and a list of the second elements.
+    pub fn unzip(&self) -> (List<A, P>, List<B, P>) {
+        (self.map(|pair| pair.0.clone()), self.map(|pair| pair.1.clone()))
+    }
+}
+
+/// An iterator over a [list](struct.List.html).
+pub struct Iter<'a, A: 'a, P: 'a + PointerKind = RcKind> {
+    list: &'a List<A, P>
+}
+
+impl<'a, A, P: PointerKind> Iter<'a, A, P> {
+    /// Returns the list of elements which have not yet been iterated over.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nth_cons_list::{cons, nil};
+    ///
+    /// let list = cons(1, cons(2, cons(3, nil())));
+    /// let mut iter = list.iter();
+    /// iter.next();
+    /// assert_eq!(*iter.as_list(), cons(2, cons(3, nil())));
+    /// ```
+    pub fn as_list(&self) -> &'a List<A, P> {
+        self.list
+    }
+}
+
+impl<'a, A, P: PointerKind> Iterator for Iter<'a, A, P> {
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.list.node {
+            Some(ref node) => {
+                self.list = &node.tail;
+                Some(&node.head)
+            }
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.list.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, A, P: PointerKind> ExactSizeIterator for Iter<'a, A, P> {}
+
+impl<'a, A: 'a, P: PointerKind> IntoIterator for &'a List<A, P> {
+    type Item = &'a A;
+    type IntoIter = Iter<'a, A, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the suffixes of a [list](struct.List.html).
+pub struct Tails<'a, A: 'a, P: 'a + PointerKind = RcKind> {
+    list: Option<&'a List<A, P>>
+}
+
+impl<'a, A, P: PointerKind> Iterator for Tails<'a, A, P> {
+    type Item = List<A, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let list = self.list?;
+        self.list = list.node.as_ref().map(|node| &node.tail);
+        Some(list.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.list.map_or(0, |list| list.len() + 1);
+        (len, Some(len))
+    }
+}
+
+impl<'a, A, P: PointerKind> ExactSizeIterator for Tails<'a, A, P> {}
+
+/// An iterator that moves out of a [list](struct.List.html).
+///
+/// Elements of nodes which are uniquely owned by the list are moved
+/// out of them; elements of nodes which are shared with another list
+/// are cloned instead.
+pub struct IntoIter<A, P: PointerKind = RcKind> {
+    list: List<A, P>
+}
+
+impl<A: Clone, P: PointerKind> Iterator for IntoIter<A, P> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.list.len();
+        (len, Some(len))
+    }
+}
+
+impl<A: Clone, P: PointerKind> ExactSizeIterator for IntoIter<A, P> {}
+
+impl<A: Clone, P: PointerKind> IntoIterator for List<A, P> {
+    type Item = A;
+    type IntoIter = IntoIter<A, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<A, P: PointerKind> FromIterator<A> for List<A, P> {
+    fn from_iter<T: IntoIterator<Item=A>>(iter: T) -> Self {
+        List::prepend_iter(iter, List::nil())
+    }
+}
+
+impl<A: Clone, P: PointerKind> Extend<A> for List<A, P> {
+    /// Appends the elements of the specified iterator to the end of
+    /// this list.
+    ///
+    /// Elements of this list are moved into the new list where its nodes
+    /// are uniquely owned, and cloned otherwise.
+    fn extend<T: IntoIterator<Item=A>>(&mut self, iter: T) {
+        let list = mem::take(self);
+        *self = List::prepend_iter(list.into_iter().chain(iter), List::nil());
+    }
+}
+
+impl<A: Clone, P: PointerKind> Add for List<A, P> {
+    type Output = List<A, P>;
+
+    /// Returns a list containing the elements of this list followed by
+    /// the elements of the specified list, which is shared as its tail.
+    ///
+    /// Elements of this list are moved into the new list where its nodes
+    /// are uniquely owned, and cloned otherwise.
+    fn add(self, other: List<A, P>) -> List<A, P> {
+        List::prepend_iter(self, other)
+    }
+}
+
+impl<A: Display, P: PointerKind> Display for List<A, P> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut list = self;
+
+        while let Some(ref node) = list.node {
+            write!(f, "{} :: ", node.head)?;
+            list = &node.tail;
+        }
+        write!(f, "Nil")
+    }
+}
+
+impl<A: Debug, P: PointerKind> Debug for List<A, P> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut list = self;
+
+        while let Some(ref node) = list.node {
+            write!(f, "{:?} :: ", node.head)?;
+            list = &node.tail;
+        }
+        write!(f, "Nil")
+    }
+}
+
+impl<A: PartialEq, P: PointerKind> PartialEq for List<A, P> {
+    /// Tests whether the lists contain equal elements in the same order.
+    ///
+    /// Lists of different lengths are unequal without comparing any
+    /// elements, and the comparison stops as soon as both lists reach
+    /// the same shared node; as a result, elements which are not equal
+    /// to themselves (such as `NaN`) are considered equal when shared.
+    fn eq(&self, other: &List<A, P>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        let mut a = self;
+        let mut b = other;
+        while !a.ptr_eq(b) {
+            match (a.uncons(), b.uncons()) {
+                (Some((x, xs)), Some((y, ys))) if x == y => {
+                    a = xs;
+                    b = ys;
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl<A: Eq, P: PointerKind> Eq for List<A, P> {}
+
+impl<A: Hash, P: PointerKind> Hash for List<A, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<A: PartialOrd, P: PointerKind> PartialOrd for List<A, P> {
+    /// Compares the lists lexicographically.
+    ///
+    /// The comparison stops as soon as both lists reach the same
+    /// shared node.
+    fn partial_cmp(&self, other: &List<A, P>) -> Option<Ordering> {
+        let mut a = self;
+        let mut b = other;
+        while !a.ptr_eq(b) {
+            match (a.uncons(), b.uncons()) {
+                (Some((x, xs)), Some((y, ys))) => match x.partial_cmp(y) {
+                    Some(Ordering::Equal) => {
+                        a = xs;
+                        b = ys;
+                    }
+                    ord => return ord,
+                },
+                (Some(_), None) => return Some(Ordering::Greater),
+                (None, _) => return Some(Ordering::Less),
+            }
+        }
+        Some(Ordering::Equal)
+    }
+}
+
+impl<A: Ord, P: PointerKind> Ord for List<A, P> {
+    /// Compares the lists lexicographically.
+    ///
+    /// The comparison stops as soon as both lists reach the same
+    /// shared node.
+    fn cmp(&self, other: &List<A, P>) -> Ordering {
+        let mut a = self;
+        let mut b = other;
+        while !a.ptr_eq(b) {
+            match (a.uncons(), b.uncons()) {
+                (Some((x, xs)), Some((y, ys))) => match x.cmp(y) {
+                    Ordering::Equal => {
+                        a = xs;
+                        b = ys;
+                    }
+                    ord => return ord,
+                },
+                (Some(_), None) => return Ordering::Greater,
+                (None, _) => return Ordering::Less,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl<A, P: PointerKind> Default for List<A, P> {
+    fn default() -> Self {
+        List::nil()
+    }
+}
+
+impl<A, P: PointerKind> Drop for List<A, P> {
+    /// Drops the list without recursing once per element.
+    ///
+    /// The default drop glue would recurse through every uniquely owned
+    /// tail, overflowing the stack for long lists. Instead, tails are
+    /// unlinked one at a time; unlinking stops at the first node that is
+    /// shared with another list, which is left intact.
+    fn drop(&mut self) {
+        let mut next = self.node.take();
+        while let Some(ptr) = next {
+            match Link::try_unwrap(ptr) {
+                Ok(mut node) => next = node.tail.node.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::collections::hash_map::DefaultHasher;
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn test_nil() {
+        let nil = nil::<i32>();
+        assert!(nil.is_empty());
+        assert_eq!(nil.len(), 0);
+        assert!(nil.head_opt().is_none());
+        assert!(nil.tail_opt().is_none());
+    }
+
+    #[test]
+    fn test_nil_const() {
+        const EMPTY: List<i32> = nil();
+        const ARC_EMPTY: ArcList<i32> = ArcList::nil();
+
+        assert!(EMPTY.is_empty());
+        assert!(ARC_EMPTY.is_empty());
+        assert_eq!(cons(1, EMPTY), cons(1, nil()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nil_panic_head() {
+        nil::<i32>().head();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nil_panic_tail() {
+        nil::<i32>().tail();
+    }
+
+    #[test]
+    fn test_try_head_tail() {
+        let a = list![1, 2];
+        assert_eq!(a.try_head(), Ok(&1));
+        assert_eq!(a.try_tail(), Ok(list![2]));
+        assert_eq!(nil::<i32>().try_head(), Err(EmptyListError));
+        assert_eq!(nil::<i32>().try_tail(), Err(EmptyListError));
+        assert_eq!(EmptyListError.to_string(), "list is empty");
+
+        fn second(list: &List<i32>) -> result::Result<i32, Box<dyn Error>> {
+            Ok(*list.try_tail()?.try_head()?)
+        }
+        assert_eq!(second(&a).unwrap(), 2);
+        assert!(second(&list![1]).is_err());
+    }
+
+    #[test]
+    fn test_cons() {
+        let nil = nil();
+        let a = cons(3, cons(2, cons(1, nil.clone())));
+        let b = cons(4, a.clone());
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 4);
+
+        let list = b;
+        assert_eq!(*list.head(), 4);
+        let list = list.tail();
+        assert_eq!(*list.head(), 3);
+        let list = list.tail();
+        assert_eq!(*list.head(), 2);
+        let list = list.tail();
+        assert_eq!(*list.head(), 1);
+        let list = list.tail();
+        assert!(list.is_empty());
+
+        let list = cons(0, nil.clone());
+        assert_eq!(*list.head_opt().unwrap(), 0);
+        let list = list.tail_opt().unwrap();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_list_macro() {
+        let empty: List<i32> = list![];
+        assert_eq!(empty, nil());
+        assert_eq!(list![1], cons(1, nil()));
+        assert_eq!(list![1, 2, 3], cons(1, cons(2, cons(3, nil()))));
+        assert_eq!(list![1, 2, 3,], list![1, 2, 3]);
+
+        let tail = list![3, 4];
+        let a = list![1, 2; tail.clone()];
+        assert_eq!(a, list![1, 2, 3, 4]);
+        assert_eq!(a.len(), 4);
+        assert_eq!(list![0; a.clone()], list![0, 1, 2, 3, 4]);
+
+        let arc = list![1, 2; ArcList::nil()];
+        assert_eq!(arc, ArcList::cons(1, ArcList::cons(2, ArcList::nil())));
+
+        // Elements and tail are evaluated from left to right
+        let order = Cell::new(list![]);
+        let record = |n: i32| {
+            order.set(cons(n, order.take()));
+            n
+        };
+        let b = list![record(1), record(2); list![record(3)]];
+        assert_eq!(b, list![1, 2, 3]);
+        assert_eq!(order.take(), list![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_head_mut() {
+        let mut a = list![1, 2, 3];
+        *a.head_mut().unwrap() = 4;
+        assert_eq!(a, list![4, 2, 3]);
+
+        let b = a.clone();
+        *a.head_mut().unwrap() = 5;
+        assert_eq!(a, list![5, 2, 3]);
+        assert_eq!(b, list![4, 2, 3]);
+        assert!(a.tail().ptr_eq(&b.tail()));
+        assert_eq!(a.len(), 3);
+
+        assert!(nil::<i32>().head_mut().is_none());
+    }
+
+    #[test]
+    fn test_try_head_mut() {
+        let mut a = list![1, 2, 3];
+        *a.try_head_mut().unwrap() = 4;
+        assert_eq!(a, list![4, 2, 3]);
+
+        let b = a.clone();
+        assert!(a.try_head_mut().is_none());
+        drop(b);
+        assert!(a.try_head_mut().is_some());
+
+        // The first node of `c` is also the second node of `a`
+        let mut c = a.tail();
+        assert!(c.try_head_mut().is_none());
+        assert!(nil::<i32>().try_head_mut().is_none());
+    }
+
+    #[test]
+    fn test_stack() {
+        let mut stack = nil();
+        assert_eq!(stack.peek(), None);
+        stack.push_front(1);
+        stack.push_front(2);
+        assert_eq!(stack.peek(), Some(&2));
+        assert_eq!(stack.len(), 2);
+
+        let snapshot = stack.clone();
+        stack.push_front(3);
+        assert_eq!(stack.pop_front(), Some(3));
+        assert!(stack.ptr_eq(&snapshot));
+        assert_eq!(stack.pop_front(), Some(2));
+        assert_eq!(stack.pop_front(), Some(1));
+        assert_eq!(stack.pop_front(), None);
+        assert!(stack.is_empty());
+        assert_eq!(snapshot, list![2, 1]);
+    }
+
+    #[test]
+    fn test_pop_front_moves_unique() {
+        let mut stack = list![Rc::new(1)];
+        let shared = list![Rc::new(2)];
+        stack.push_front(Rc::new(3));
+
+        let elem = stack.pop_front().unwrap();
+        assert_eq!(Rc::strong_count(&elem), 1);
+
+        let mut other = shared.clone();
+        let elem = other.pop_front().unwrap();
+        assert_eq!(Rc::strong_count(&elem), 2);
+        assert_eq!(shared.len(), 1);
+    }
+
+    #[test]
+    fn test_uncons_view() {
+        let nil = nil();
+        let a = cons(2, cons(1, nil.clone()));
+
+        let (head, tail) = a.uncons().unwrap();
+        assert_eq!(*head, 2);
+        assert_eq!(*tail, cons(1, nil.clone()));
+        assert!(nil.uncons().is_none());
+
+        match a.view() {
+            ListRef::Cons(head, tail) => {
+                assert_eq!(*head, 2);
+                assert_eq!(*tail, cons(1, nil.clone()));
+            }
+            ListRef::Nil => panic!("expected a non-empty list"),
+        }
+        match nil.view() {
+            ListRef::Cons(_, _) => panic!("expected an empty list"),
+            ListRef::Nil => {}
+        }
+    }
+
+    #[test]
+    fn test_len() {
+        let a = cons(3, cons(2, cons(1, nil())));
+        let b = cons(4, a.clone());
+        let c = cons(5, a.tail());
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 4);
+        assert_eq!(c.len(), 3);
+
+        let mut iter = b.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 2);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn test_get() {
+        let a = list![1, 2, 3];
+        assert_eq!(a.get(0), Some(&1));
+        assert_eq!(a.get(2), Some(&3));
+        assert_eq!(a.get(3), None);
+        assert_eq!(a.get(usize::MAX), None);
+        assert_eq!(nil::<i32>().get(0), None);
 
-impl<A> Clone for List<A> {
-    /// Clones the list by cloning a reference counted pointer to
-    /// the list's contents; this operation is very cheap.
-    fn clone(&self) -> Self {
-        List { rc: Rc::clone(&self.rc) }
+        assert_eq!(a.last(), Some(&3));
+        assert_eq!(list![1].last(), Some(&1));
+        assert_eq!(nil::<i32>().last(), None);
     }
-}
 
-/// Prepends the specified element at the head of the specified list.
-pub fn cons<A>(head: A, tail: List<A>) -> List<A> {
-    List { rc: Rc::new(Cons(head, tail)) }
-}
+    #[test]
+    fn test_nth_tail_drop() {
+        let a = list![1, 2, 3];
+        assert!(a.nth_tail(0).unwrap().ptr_eq(&a));
+        assert_eq!(*a.nth_tail(2).unwrap(), list![3]);
+        assert!(a.nth_tail(3).unwrap().is_empty());
+        assert!(a.nth_tail(4).is_none());
 
-/// Returns the empty list.
-pub fn nil<A>() -> List<A> {
-    List { rc: Rc::new(Nil) }
-}
+        assert!(a.drop(1).ptr_eq(&a.tail()));
+        assert_eq!(a.drop(0), a);
+        assert_eq!(a.drop(3), nil());
+        assert_eq!(a.drop(10), nil());
+        assert_eq!(nil::<i32>().drop(1), nil());
+    }
 
-impl<A> List<A> {
-    /// Returns the first element of the list.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the list is empty.
-    pub fn head(&self) -> &A {
-        match *self.rc {
-            Cons(ref h, _) => &h,
-            Nil => panic!("`head` on empty List"),
-        }
+    #[test]
+    fn test_take_split_at() {
+        let a = list![1, 2, 3];
+        assert_eq!(a.take(0), nil());
+        assert_eq!(a.take(2), list![1, 2]);
+        assert_eq!(a.take(2).len(), 2);
+        assert!(a.take(3).ptr_eq(&a));
+        assert!(a.take(10).ptr_eq(&a));
+
+        let (init, rest) = a.split_at(1);
+        assert_eq!(init, list![1]);
+        assert!(rest.ptr_eq(&a.tail()));
+        assert_eq!(a.split_at(5), (a.clone(), nil()));
+        assert_eq!(nil::<i32>().split_at(1), (nil(), nil()));
     }
 
-    /// Returns a list containing all elements except the first.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the list is empty.
-    pub fn tail(&self) -> List<A> {
-        match *self.rc {
-            Cons(_, ref t) => t.clone(),
-            Nil => panic!("`tail` on empty List"),
-        }
+    #[test]
+    fn test_take_drop_while() {
+        let a = list![1, 2, 3, 1];
+        assert_eq!(a.take_while(|&i| i < 3), list![1, 2]);
+        assert_eq!(a.take_while(|_| false), nil());
+        assert!(a.take_while(|_| true).ptr_eq(&a));
+
+        assert_eq!(a.drop_while(|&i| i < 3), list![3, 1]);
+        assert!(a.drop_while(|&i| i < 2).ptr_eq(&a.tail()));
+        assert_eq!(a.drop_while(|_| true), nil());
+        assert!(a.drop_while(|_| false).ptr_eq(&a));
     }
 
-    /// Returns the first element of the list, or `None` if
-    /// this list is empty.
-    pub fn head_opt(&self) -> Option<&A> {
-        match *self.rc {
-            Cons(ref h, _) => Some(&h),
-            Nil => None,
-        }
+    #[test]
+    fn test_updated() {
+        let a = list![1, 2, 3];
+        let b = a.updated(1, 5);
+        assert_eq!(b, list![1, 5, 3]);
+        assert_eq!(b.len(), 3);
+        assert!(b.drop(2).ptr_eq(&a.drop(2)));
+        assert_eq!(a.updated(0, 0), list![0, 2, 3]);
+        assert_eq!(a.updated(2, 0), list![1, 2, 0]);
+        assert_eq!(a, list![1, 2, 3]);
     }
 
-    /// Returns a list containing all elements except the first,
-    /// or `None` if this list is empty.
-    pub fn tail_opt(&self) -> Option<List<A>> {
-        match *self.rc {
-            Cons(_, ref t) => Some(t.clone()),
-            Nil => None,
-        }
+    #[test]
+    #[should_panic]
+    fn test_updated_out_of_range() {
+        list![1, 2, 3].updated(3, 0);
     }
 
-    /// Tests whether this list is empty.
-    pub fn is_empty(&self) -> bool {
-        match *self.rc {
-            Cons(_, _) => false,
-            Nil => true,
-        }
+    #[test]
+    fn test_insert_at() {
+        let a = list![1, 2, 3];
+        let b = a.insert_at(1, 5);
+        assert_eq!(b, list![1, 5, 2, 3]);
+        assert_eq!(b.len(), 4);
+        assert!(b.drop(2).ptr_eq(&a.drop(1)));
+        assert_eq!(a.insert_at(0, 0), list![0, 1, 2, 3]);
+        assert!(a.insert_at(0, 0).tail().ptr_eq(&a));
+        assert_eq!(a.insert_at(3, 0), list![1, 2, 3, 0]);
+        assert_eq!(nil().insert_at(0, 0), list![0]);
     }
 
-    /// Returns the length of this list.
-    pub fn len(&self) -> usize {
-        self.iter().count()
+    #[test]
+    #[should_panic]
+    fn test_insert_at_out_of_range() {
+        list![1, 2, 3].insert_at(4, 0);
     }
 
-    /// Returns an iterator over the elements of this list.
-    pub fn iter(&self) -> Iter<A> {
-        Iter { list: self }
+    #[test]
+    fn test_remove_at() {
+        let a = list![1, 2, 3];
+        let b = a.remove_at(1);
+        assert_eq!(b, list![1, 3]);
+        assert_eq!(b.len(), 2);
+        assert!(b.tail().ptr_eq(&a.drop(2)));
+        assert!(a.remove_at(0).ptr_eq(&a.tail()));
+        assert_eq!(a.remove_at(2), list![1, 2]);
     }
 
-    /// Returns a list with the elements in reverse order.
-    pub fn reverse(&self) -> List<A> where A: Clone {
-        let mut list = nil();
-        let mut rest = self;
+    #[test]
+    #[should_panic]
+    fn test_remove_at_out_of_range() {
+        nil::<i32>().remove_at(0);
+    }
 
-        while let Cons(ref h, ref t) = *rest.rc {
-            list = cons(h.clone(), list);
-            rest = t;
-        }
+    #[test]
+    fn test_remove_first() {
+        let a = list![1, 2, 3, 2];
+        let b = a.remove_first(|&i| i == 2);
+        assert_eq!(b, list![1, 3, 2]);
+        assert!(b.tail().ptr_eq(&a.drop(2)));
+        assert!(a.remove_first(|&i| i == 4).ptr_eq(&a));
+        assert_eq!(nil::<i32>().remove_first(|_| true), nil());
+    }
 
-        list
+    #[test]
+    fn test_search() {
+        let a = list![1, 2, 3, 2];
+        assert!(a.contains(&3));
+        assert!(!a.contains(&4));
+        assert!(!nil().contains(&1));
+
+        assert_eq!(a.find(|&i| i > 1), Some(&2));
+        assert_eq!(a.find(|&i| i > 3), None);
+        assert_eq!(a.position(|&i| i > 1), Some(1));
+        assert_eq!(a.position(|&i| i > 3), None);
+
+        assert!(a.any(|&i| i == 3));
+        assert!(!a.any(|&i| i == 4));
+        assert!(!nil::<i32>().any(|_| true));
+        assert!(a.all(|&i| i > 0));
+        assert!(!a.all(|&i| i > 1));
+        assert!(nil::<i32>().all(|_| false));
+
+        assert_eq!(a.count_by(|&i| i == 2), 2);
+        assert_eq!(a.count_by(|&i| i == 4), 0);
     }
 
-    /// Creates a new list from a `DoubleEndedIterator`.
-    pub fn from_double_ended_iter<I: DoubleEndedIterator<Item=A>>(iter: I) -> List<A> {
-        let mut list = nil();
-        for elem in iter.rev() {
-            list = cons(elem, list);
-        }
-        list
+    #[test]
+    fn test_find_tail() {
+        let a = list![1, 2, 3, 2];
+        let b = a.find_tail(|&i| i == 2);
+        assert_eq!(b, list![2, 3, 2]);
+        assert!(b.ptr_eq(&a.tail()));
+        assert!(a.find_tail(|&i| i == 1).ptr_eq(&a));
+        assert_eq!(a.find_tail(|&i| i == 4), nil());
     }
-}
 
-/// An iterator over a [list](struct.List.html).
-pub struct Iter<'a, A: 'a> {
-    list: &'a List<A>
-}
+    #[test]
+    fn test_reverse() {
+        let nil = nil();
+        let a = cons(3, cons(2, cons(1, nil.clone())));
 
-impl<'a, A> Iterator for Iter<'a, A> {
-    type Item = &'a A;
+        assert_eq!(a.reverse(), cons(1, cons(2, cons(3, nil.clone()))));
+        assert_eq!(nil.reverse(), nil);
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match *self.list.rc {
-            Cons(ref h, ref t) => {
-                self.list = t;
-                Some(h)
-            }
-            Nil => None,
-        }
+    #[test]
+    fn test_map() {
+        let a = list![1, 2, 3];
+        assert_eq!(a.map(|i| i * 2), list![2, 4, 6]);
+        assert_eq!(a.map(|i| i.to_string()), list!["1".to_string(), "2".to_string(), "3".to_string()]);
+        assert_eq!(a.map(|i| i * 2).len(), 3);
+        assert_eq!(nil::<i32>().map(|i| i * 2), nil());
     }
-}
 
-impl<'a, A: 'a> IntoIterator for &'a List<A> {
-    type Item = &'a A;
-    type IntoIter = Iter<'a, A>;
+    #[test]
+    fn test_filter() {
+        let a = list![1, 2, 3, 4, 5, 6];
+        assert_eq!(a.filter(|i| i % 2 == 0), list![2, 4, 6]);
+        assert_eq!(a.filter(|_| false), nil());
+        assert_eq!(nil::<i32>().filter(|_| true), nil());
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+        // The suffix following the last removed element is shared
+        let b = a.filter(|&i| i != 3);
+        assert_eq!(b, list![1, 2, 4, 5, 6]);
+        assert_eq!(b.len(), 5);
+        assert!(b.tail().tail().ptr_eq(&a.tail().tail().tail()));
+        assert!(a.filter(|_| true).ptr_eq(&a));
+
+        // The predicate is called once per element
+        let mut calls = 0;
+        a.filter(|&i| {
+            calls += 1;
+            i < 3
+        });
+        assert_eq!(calls, 6);
     }
-}
 
-impl<A> FromIterator<A> for List<A> {
-    fn from_iter<T: IntoIterator<Item=A>>(iter: T) -> Self {
-        let elems: Vec<A> = iter.into_iter().collect();
-        List::from_double_ended_iter(elems.into_iter())
+    #[test]
+    fn test_filter_map_flat_map() {
+        let a = list!["1", "x", "3"];
+        assert_eq!(a.filter_map(|s| s.parse::<i32>().ok()), list![1, 3]);
+        assert_eq!(nil::<&str>().filter_map(|s| s.parse::<i32>().ok()), nil());
+
+        let b = list![1, 2, 3];
+        assert_eq!(b.flat_map(|&i| vec![i; i]), list![1, 2, 2, 3, 3, 3]);
+        assert_eq!(b.flat_map(|&i| list![i, i * 10]), list![1, 10, 2, 20, 3, 30]);
+        assert_eq!(b.flat_map(|_| None::<i32>), nil());
     }
-}
 
-impl<A: Display> Display for List<A> {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        let mut list = self;
+    #[test]
+    fn test_folds() {
+        let a = list![1, 2, 3];
+        assert_eq!(a.fold_left(String::new(), |acc, i| format!("({}{})", acc, i)), "(((1)2)3)");
+        assert_eq!(a.fold_right(String::new(), |i, acc| format!("({}{})", i, acc)), "(1(2(3)))");
+        assert_eq!(nil::<i32>().fold_left(0, |acc, i| acc + i), 0);
+        assert_eq!(nil::<i32>().fold_right(0, |i, acc| acc + i), 0);
 
-        while let Cons(ref h, ref t) = *list.rc {
-            write!(f, "{} :: ", *h)?;
-            list = t;
-        }
-        write!(f, "Nil")
+        assert_eq!(a.reduce_left(|acc, i| acc - i), Some(-4));
+        assert_eq!(a.reduce_right(|i, acc| i - acc), Some(2));
+        assert_eq!(list![5].reduce_left(|acc, i| acc - i), Some(5));
+        assert_eq!(list![5].reduce_right(|i, acc| i - acc), Some(5));
+        assert_eq!(nil::<i32>().reduce_left(|acc, i| acc - i), None);
+        assert_eq!(nil::<i32>().reduce_right(|i, acc| i - acc), None);
+
+        let long = long_list(1_000_000);
+        assert_eq!(long.fold_right(0, |&i, acc| acc + i), 499_999_500_000);
+        assert_eq!(long.reduce_right(|&i, acc| acc.max(i)), Some(999_999));
     }
-}
 
-impl<A: Debug> Debug for List<A> {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        let mut list = self;
+    #[test]
+    fn test_scans() {
+        let a = list![1, 2, 3];
+        assert_eq!(a.scan_left(0, |acc, i| acc + i), list![0, 1, 3, 6]);
+        assert_eq!(a.scan_right(0, |i, acc| acc + i), list![6, 5, 3, 0]);
+        assert_eq!(a.scan_left(0, |acc, i| acc + i).len(), 4);
+        assert_eq!(a.scan_right(0, |i, acc| acc + i).len(), 4);
+        assert_eq!(nil::<i32>().scan_left(0, |acc, i| acc + i), list![0]);
+        assert_eq!(nil::<i32>().scan_right(0, |i, acc| acc + i), list![0]);
 
-        while let Cons(ref h, ref t) = *list.rc {
-            write!(f, "{:?} :: ", *h)?;
-            list = t;
-        }
-        write!(f, "Nil")
+        let long = long_list(1_000_000);
+        assert_eq!(*long.scan_right(0, |&i, acc| acc + i).head(), 499_999_500_000);
     }
-}
 
-impl<A: PartialEq> PartialEq for List<A> {
-    fn eq(&self, other: &List<A>) -> bool {
-        self.iter().eq(other.iter())
+    #[test]
+    fn test_append() {
+        let a = list![1, 2];
+        let b = list![3, 4];
+
+        let c = a.append(&b);
+        assert_eq!(c, list![1, 2, 3, 4]);
+        assert_eq!(c.len(), 4);
+        assert!(c.tail().tail().ptr_eq(&b));
+        assert!(nil().append(&b).ptr_eq(&b));
+        assert_eq!(a.append(&nil()), a);
+
+        assert_eq!(a.clone() + b.clone(), list![1, 2, 3, 4]);
+        assert_eq!(nil() + b.clone(), b);
     }
-}
 
-impl<A: Eq> Eq for List<A> {}
+    #[test]
+    fn test_add_moves_unique() {
+        let shared = list![Rc::new(2)];
+        let a = list![Rc::new(1); shared.clone()];
+        let b = list![Rc::new(3)];
+
+        let c = a + b.clone();
+        assert_eq!(c.iter().map(|e| **e).collect::<Vec<_>>(), vec![1, 2, 3]);
 
-impl<A: Hash> Hash for List<A> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.iter().for_each(|elem| elem.hash(state));
+        // Uniquely owned elements are moved, shared ones are cloned
+        assert_eq!(Rc::strong_count(c.head()), 1);
+        assert_eq!(Rc::strong_count(c.get(1).unwrap()), 2);
+        assert!(c.drop(2).ptr_eq(&b));
     }
-}
 
-impl<A: PartialOrd> PartialOrd for List<A> {
-    fn partial_cmp(&self, other: &List<A>) -> Option<Ordering> {
-        self.iter().partial_cmp(other.iter())
+    #[test]
+    fn test_concat() {
+        let last = list![5, 6];
+        let lists = list![list![1, 2], nil(), list![3], list![4]; list![last.clone()]];
+
+        let a = lists.concat();
+        assert_eq!(a, list![1, 2, 3, 4, 5, 6]);
+        assert_eq!(a.len(), 6);
+        assert!(a.tail().tail().tail().tail().ptr_eq(&last));
+        assert_eq!(nil::<List<i32>>().concat(), nil());
+        assert_eq!(list![nil::<i32>(), nil()].concat(), nil());
     }
-}
 
-impl<A: Ord> Ord for List<A> {
-    fn cmp(&self, other: &List<A>) -> Ordering {
-        self.iter().cmp(other.iter())
+    #[test]
+    fn test_extend() {
+        let mut a = list![1, 2];
+        let b = a.clone();
+        a.extend(vec![3, 4]);
+        assert_eq!(a, list![1, 2, 3, 4]);
+        assert_eq!(a.len(), 4);
+        assert_eq!(b, list![1, 2]);
+
+        let mut c = nil();
+        c.extend(list![1, 2].iter().cloned());
+        c.extend(None);
+        assert_eq!(c, list![1, 2]);
     }
-}
 
-impl<A> Default for List<A> {
-    fn default() -> Self {
-        nil()
+    #[test]
+    fn test_zip() {
+        let a = list![1, 2, 3];
+        let b = list!["a", "b"];
+        assert_eq!(a.zip(&b), list![(1, "a"), (2, "b")]);
+        assert_eq!(b.zip(&a), list![("a", 1), ("b", 2)]);
+        assert_eq!(a.zip(&b).len(), 2);
+        assert_eq!(a.zip(&nil::<i32>()), nil());
+
+        assert_eq!(a.zip_with(&a.tail(), |x, y| x * y), list![2, 6]);
+        assert_eq!(nil::<i32>().zip_with(&a, |x, y| x * y), nil());
+
+        let long = long_list(1_000_000);
+        assert_eq!(long.zip(&long).len(), 1_000_000);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::hash_map::DefaultHasher;
+    #[test]
+    fn test_zip_longest() {
+        let a = list![1, 2, 3];
+        let b = list!["a"];
+        assert_eq!(a.zip_longest(&b), list![(Some(1), Some("a")), (Some(2), None), (Some(3), None)]);
+        assert_eq!(b.zip_longest(&a), list![(Some("a"), Some(1)), (None, Some(2)), (None, Some(3))]);
+        assert_eq!(a.zip_longest(&b).len(), 3);
+        assert_eq!(nil::<i32>().zip_longest(&nil::<i32>()), nil());
+    }
 
     #[test]
-    fn test_nil() {
-        let nil = nil::<i32>();
-        assert!(nil.is_empty());
-        assert_eq!(nil.len(), 0);
-        assert!(nil.head_opt().is_none());
-        assert!(nil.tail_opt().is_none());
+    fn test_unzip() {
+        let a = list![(1, "a"), (2, "b"), (3, "c")];
+        let (nums, strs) = a.unzip();
+        assert_eq!(nums, list![1, 2, 3]);
+        assert_eq!(strs, list!["a", "b", "c"]);
+        assert_eq!(nums.zip(&strs), a);
+        assert_eq!(nil::<(i32, i32)>().unzip(), (nil(), nil()));
+    }
+
+    #[test]
+    fn test_partition() {
+        let a = list![1, 2, 3, 4, 5];
+        let (even, odd) = a.partition(|i| i % 2 == 0);
+        assert_eq!(even, list![2, 4]);
+        assert_eq!(odd, list![1, 3, 5]);
+        assert_eq!(odd.len(), 3);
+        assert_eq!(nil::<i32>().partition(|_| true), (nil(), nil()));
+    }
+
+    #[test]
+    fn test_span() {
+        let a = list![1, 2, 3, 1];
+        let (init, rest) = a.span(|&i| i < 3);
+        assert_eq!(init, list![1, 2]);
+        assert!(rest.ptr_eq(&a.drop(2)));
+        assert_eq!(a.span(|_| false), (nil(), a.clone()));
+        assert_eq!(a.span(|_| true), (a.clone(), nil()));
+
+        let (init, rest) = a.break_at(|&i| i == 3);
+        assert_eq!(init, list![1, 2]);
+        assert_eq!(rest, list![3, 1]);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let a = list![1, 1, 2, 3, 3, 3];
+        let groups = a.group_by(|x, y| x == y);
+        assert_eq!(groups, list![list![1, 1], list![2], list![3, 3, 3]]);
+        assert!(groups.last().unwrap().ptr_eq(&a.drop(3)));
+        assert_eq!(list![1, 2, 3, 5, 6].group_by(|x, y| y - x == 1), list![list![1, 2, 3], list![5, 6]]);
+        assert_eq!(list![1].group_by(|x, y| x == y), list![list![1]]);
+        assert_eq!(nil::<i32>().group_by(|x, y| x == y), nil());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let a = list![1, 2, 3, 4, 5];
+        let chunks = a.chunks(2);
+        assert_eq!(chunks, list![list![1, 2], list![3, 4], list![5]]);
+        assert!(chunks.last().unwrap().ptr_eq(&a.drop(4)));
+        assert_eq!(a.chunks(5), list![a.clone()]);
+        assert_eq!(a.chunks(10), list![a.clone()]);
+        assert_eq!(list![1, 2, 3, 4].chunks(2), list![list![1, 2], list![3, 4]]);
+        assert_eq!(nil::<i32>().chunks(2), nil());
     }
 
     #[test]
     #[should_panic]
-    fn test_nil_panic_head() {
-        nil::<i32>().head();
+    fn test_chunks_zero() {
+        list![1, 2].chunks(0);
+    }
+
+    #[test]
+    fn test_sliding() {
+        let a = list![1, 2, 3, 4];
+        let windows = a.sliding(2);
+        assert_eq!(windows, list![list![1, 2], list![2, 3], list![3, 4]]);
+        assert!(windows.last().unwrap().ptr_eq(&a.drop(2)));
+        assert_eq!(a.sliding(4), list![a.clone()]);
+        assert_eq!(a.sliding(5), nil());
+        assert_eq!(a.sliding(1).len(), 4);
+        assert_eq!(nil::<i32>().sliding(1), nil());
     }
 
     #[test]
     #[should_panic]
-    fn test_nil_panic_tail() {
-        nil::<i32>().tail();
+    fn test_sliding_zero() {
+        list![1, 2].sliding(0);
     }
 
     #[test]
-    fn test_cons() {
-        let nil = nil();
-        let a = cons(3, cons(2, cons(1, nil.clone())));
-        let b = cons(4, a.clone());
+    fn test_sort() {
+        let a = list![3, 1, 2, 5, 4];
+        assert_eq!(a.sort(), list![1, 2, 3, 4, 5]);
+        assert_eq!(a.sort().len(), 5);
+        assert_eq!(a.sort_by(|x, y| y.cmp(x)), list![5, 4, 3, 2, 1]);
+        assert_eq!(nil::<i32>().sort(), nil());
 
-        assert_eq!(a.len(), 3);
-        assert_eq!(b.len(), 4);
+        // Sorting is stable
+        let b = list![(2, "a"), (1, "b"), (2, "c"), (1, "d")];
+        assert_eq!(b.sort_by_key(|pair| pair.0), list![(1, "b"), (1, "d"), (2, "a"), (2, "c")]);
+    }
 
-        let list = b;
-        assert_eq!(*list.head(), 4);
-        let list = list.tail();
-        assert_eq!(*list.head(), 3);
-        let list = list.tail();
-        assert_eq!(*list.head(), 2);
-        let list = list.tail();
-        assert_eq!(*list.head(), 1);
-        let list = list.tail();
-        assert!(list.is_empty());
+    #[test]
+    fn test_merge() {
+        let a = list![1, 3, 5];
+        let b = list![2, 3, 4, 6, 7];
 
-        let list = cons(0, nil.clone());
-        assert_eq!(*list.head_opt().unwrap(), 0);
-        let list = list.tail_opt().unwrap();
-        assert!(list.is_empty());
+        let c = a.merge(&b);
+        assert_eq!(c, list![1, 2, 3, 3, 4, 5, 6, 7]);
+        assert_eq!(c.len(), 8);
+        assert!(c.drop(6).ptr_eq(&b.drop(3)));
+        assert!(a.merge(&nil()).ptr_eq(&a));
+        assert!(nil().merge(&b).ptr_eq(&b));
+
+        // Equal elements of the first list come first
+        let d = list![(1, "a"), (2, "a")];
+        let e = list![(1, "b"), (2, "b")];
+        assert_eq!(d.merge_by(&e, |x, y| x.0.cmp(&y.0)), list![(1, "a"), (1, "b"), (2, "a"), (2, "b")]);
     }
 
     #[test]
-    fn test_reverse() {
-        let nil = nil();
-        let a = cons(3, cons(2, cons(1, nil.clone())));
+    fn test_dedup() {
+        let a = list![1, 1, 2, 3, 3, 3, 1, 4, 5];
+        let b = a.dedup();
+        assert_eq!(b, list![1, 2, 3, 1, 4, 5]);
+        assert_eq!(b.len(), 6);
+        assert!(b.drop(3).ptr_eq(&a.drop(6)));
+        assert!(b.dedup().ptr_eq(&b));
+        assert_eq!(nil::<i32>().dedup(), nil());
 
-        assert_eq!(a.reverse(), cons(1, cons(2, cons(3, nil.clone()))));
-        assert_eq!(nil.reverse(), nil);
+        let c = list![1, 2, 4, 5, 7];
+        assert_eq!(c.dedup_by(|x, prev| x - prev == 1), list![1, 4, 7]);
     }
 
     #[test]
@@ -303,9 +1892,83 @@ mod tests {
         let nil = nil();
         let a = cons(3, cons(2, cons(1, nil.clone())));
 
-        assert_eq!(a.iter().map(|i| *i).collect::<List<i32>>(), a);
+        assert_eq!(a.iter().cloned().collect::<List<i32>>(), a);
         assert_eq!(vec![3, 2, 1].into_iter().collect::<List<i32>>(), a);
         assert_eq!(List::from_double_ended_iter(vec![3, 2, 1].into_iter()), a);
+
+        let b = (1..10).filter(|i| i % 3 != 0).collect::<List<i32>>();
+        assert_eq!(b, cons(1, cons(2, cons(4, cons(5, cons(7, cons(8, nil.clone())))))));
+        let mut rest = b;
+        for len in (0..7).rev() {
+            assert_eq!(rest.len(), len);
+            rest = rest.tail_opt().unwrap_or_default();
+        }
+
+        assert_eq!(List::prepend_iter(vec![5, 4], a.clone()), cons(5, cons(4, a.clone())));
+        assert_eq!(List::prepend_iter(vec![5, 4], a.clone()).len(), 5);
+        assert!(List::prepend_iter(vec![], nil).is_empty());
+    }
+
+    #[test]
+    fn test_iter_as_list() {
+        let a = list![1, 2, 3];
+        let mut iter = a.iter();
+        assert!(iter.as_list().ptr_eq(&a));
+        iter.next();
+        assert!(iter.as_list().ptr_eq(&a.tail()));
+        iter.next();
+        iter.next();
+        assert!(iter.as_list().is_empty());
+        iter.next();
+        assert!(iter.as_list().is_empty());
+    }
+
+    #[test]
+    fn test_tails() {
+        let a = list![1, 2, 3];
+        let tails: Vec<List<i32>> = a.tails().collect();
+        assert_eq!(tails, vec![list![1, 2, 3], list![2, 3], list![3], nil()]);
+        assert!(tails[1].ptr_eq(&a.tail()));
+
+        let mut iter = a.tails();
+        assert_eq!(iter.len(), 4);
+        iter.next();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(nil::<i32>().tails().collect::<Vec<_>>(), vec![nil()]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let a = cons(3, cons(2, cons(1, nil())));
+        assert_eq!(a.clone().into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        let mut iter = a.into_iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 1]);
+
+        let mut count = 0;
+        for elem in cons(1, cons(2, nil())) {
+            count += elem;
+        }
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_into_iter_moves_unique() {
+        let shared = cons(Rc::new(2), cons(Rc::new(1), nil()));
+        let a = cons(Rc::new(4), cons(Rc::new(3), shared.clone()));
+
+        let elems: Vec<Rc<i32>> = a.into_iter().collect();
+        assert_eq!(elems.iter().map(|e| **e).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+        // Uniquely owned elements are moved out, shared ones are cloned
+        assert_eq!(Rc::strong_count(&elems[0]), 1);
+        assert_eq!(Rc::strong_count(&elems[1]), 1);
+        assert_eq!(Rc::strong_count(&elems[2]), 2);
+        assert_eq!(Rc::strong_count(&elems[3]), 2);
+        assert_eq!(shared.len(), 2);
     }
 
     #[test]
@@ -348,9 +2011,12 @@ mod tests {
 
         // hashing
         let e = cons("a", nil.clone());
-        let mut hasher = DefaultHasher::new();
+        let mut a_hasher = DefaultHasher::new();
+        let mut e_hasher = DefaultHasher::new();
+        a.hash(&mut a_hasher);
+        e.hash(&mut e_hasher);
         assert_eq!(a, e);
-        assert_eq!(a.hash(&mut hasher), e.hash(&mut hasher));
+        assert_eq!(a_hasher.finish(), e_hasher.finish());
     }
 
     #[test]
@@ -387,8 +2053,155 @@ mod tests {
         assert_eq!(nil.partial_cmp(&c), Some(Ordering::Less));
     }
 
+    #[test]
+    fn test_ptr_eq() {
+        let nil = nil();
+        let a = list![1, 2];
+        let b = a.clone();
+        let c = list![1, 2];
+
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&c));
+        assert!(a.tail().ptr_eq(&b.tail()));
+        assert!(nil.ptr_eq(&List::nil()));
+        assert!(!nil.ptr_eq(&a));
+        assert!(!a.ptr_eq(&nil));
+    }
+
+    #[test]
+    fn test_eq_cmp_shared() {
+        // Shared nodes are not compared, so `NaN`s in them are equal
+        let shared = list![f64::NAN, 2.0];
+        let a = list![1.0; shared.clone()];
+        let b = list![1.0; shared.clone()];
+        assert_eq!(a, b);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+        assert_ne!(a, list![1.0, f64::NAN, 2.0]);
+        assert_eq!(a.partial_cmp(&list![1.0, f64::NAN, 2.0]), None);
+        assert_ne!(a, list![1.0; shared.tail()]);
+        assert_eq!(list![0.0; shared.clone()].partial_cmp(&a), Some(Ordering::Less));
+
+        struct Counted<'a>(usize, &'a Cell<usize>);
+
+        impl<'a> PartialEq for Counted<'a> {
+            fn eq(&self, other: &Self) -> bool {
+                self.1.set(self.1.get() + 1);
+                self.0 == other.0
+            }
+        }
+
+        impl<'a> Eq for Counted<'a> {}
+
+        impl<'a> PartialOrd for Counted<'a> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<'a> Ord for Counted<'a> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.1.set(self.1.get() + 1);
+                self.0.cmp(&other.0)
+            }
+        }
+
+        // Only the elements in front of the shared nodes are compared
+        let calls = Cell::new(0);
+        let compared = |result: bool| {
+            assert!(result);
+            calls.replace(0)
+        };
+        let long: List<_> = List::from_double_ended_iter((0..1_000_000).map(|n| Counted(n, &calls)));
+        let c = list![Counted(1, &calls), Counted(2, &calls); long.clone()];
+        let d = list![Counted(1, &calls), Counted(2, &calls); long.clone()];
+        let e = list![Counted(1, &calls), Counted(3, &calls); long.clone()];
+        assert_eq!(compared(c == d), 2);
+        assert_eq!(compared(c != e), 2);
+        assert_eq!(compared(c == c), 0);
+        assert_eq!(compared(c != c.tail()), 0);
+        assert_eq!(compared(c.partial_cmp(&d) == Some(Ordering::Equal)), 2);
+        assert_eq!(compared(c.cmp(&d) == Ordering::Equal), 2);
+        assert_eq!(compared(c.cmp(&e) == Ordering::Less), 2);
+        assert_eq!(compared(e.cmp(&c) == Ordering::Greater), 2);
+        assert_eq!(compared(c.cmp(&c) == Ordering::Equal), 0);
+        assert_eq!(compared(c.cmp(&c.tail()) == Ordering::Less), 1);
+    }
+
     #[test]
     fn test_default() {
         assert_eq!(List::<i32>::default(), nil())
     }
+
+    #[test]
+    fn test_arc_list() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ArcList<i32>>();
+
+        let nil = ArcList::nil();
+        let a = ArcList::cons(3, ArcList::cons(2, ArcList::cons(1, nil.clone())));
+        assert_eq!(a.len(), 3);
+        assert_eq!(*a.head(), 3);
+        assert_eq!(a.tail(), vec![2, 1].into_iter().collect());
+        assert_eq!(a.reverse(), ArcList::from_double_ended_iter(vec![1, 2, 3].into_iter()));
+        assert_eq!(format!("{}", a), "3 :: 2 :: 1 :: Nil");
+        assert_eq!(ArcList::<i32>::default(), nil);
+
+        let shared = a.clone();
+        let handle = thread::spawn(move || shared.iter().sum::<i32>());
+        assert_eq!(handle.join().unwrap(), 6);
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn test_variance() {
+        // These only compile if the types are covariant in `A`
+        fn shorten<'a>(list: List<&'static str>) -> List<&'a str> { list }
+        fn shorten_arc<'a>(list: ArcList<&'static str>) -> ArcList<&'a str> { list }
+        fn shorten_iter<'a, 'b>(iter: Iter<'b, &'static str>) -> Iter<'b, &'a str> { iter }
+        fn shorten_view<'a, 'b>(view: ListRef<'b, &'static str>) -> ListRef<'b, &'a str> { view }
+        fn shorten_tails<'a, 'b>(tails: Tails<'b, &'static str>) -> Tails<'b, &'a str> { tails }
+        fn shorten_into_iter<'a>(iter: IntoIter<&'static str>) -> IntoIter<&'a str> { iter }
+        fn shorten_non_empty<'a>(list: NonEmptyList<&'static str>) -> NonEmptyList<&'a str> { list }
+
+        let local = String::from("b");
+        let list = list!["a"];
+        let list = list![&local[..]; shorten(list)];
+        assert_eq!(list, list!["b", "a"]);
+        assert_eq!(shorten_arc(ArcList::cons("a", ArcList::nil())).len(), 1);
+        assert_eq!(shorten_iter(list!["a"].iter()).count(), 1);
+        assert!(matches!(shorten_view(nil().view()), ListRef::Nil));
+        assert_eq!(shorten_tails(nil().tails()).count(), 1);
+        assert_eq!(shorten_into_iter(list!["a"].into_iter()).count(), 1);
+        assert_eq!(shorten_non_empty(NonEmptyList::cons("a", nil())).len(), 1);
+    }
+
+    fn long_list(n: usize) -> List<usize> {
+        let mut list = nil();
+        for i in 0..n {
+            list = cons(i, list);
+        }
+        list
+    }
+
+    #[test]
+    fn test_drop_long() {
+        let list = long_list(3_000_000);
+        assert_eq!(*list.head(), 2_999_999);
+        drop(list);
+    }
+
+    #[test]
+    fn test_drop_long_shared_tail() {
+        let tail = long_list(2_000_000);
+        let list = cons(1, cons(2, tail.clone()));
+        let other = cons(3, tail.clone());
+
+        drop(list);
+        assert_eq!(tail.len(), 2_000_000);
+        assert_eq!(*tail.head(), 1_999_999);
+
+        drop(tail);
+        assert_eq!(other.len(), 2_000_001);
+        drop(other);
+    }
 }
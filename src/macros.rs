@@ -0,0 +1,44 @@
+//! Macros for constructing lists.
+
+/// Creates a [`List`](struct.List.html) containing the specified elements.
+///
+/// `list![a, b, c]` creates a list containing `a, b, c`, and
+/// `list![a, b; tail]` creates a list containing `a, b` followed by the
+/// elements of `tail`, which is shared rather than copied.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate nth_cons_list;
+///
+/// use nth_cons_list::{cons, nil, List};
+///
+/// # fn main() {
+/// let empty: List<i32> = list![];
+/// assert_eq!(empty, nil());
+///
+/// let list = list![1, 2, 3];
+/// assert_eq!(list, cons(1, cons(2, cons(3, nil()))));
+///
+/// let longer = list![-1, 0; list.clone()];
+/// assert_eq!(longer, list![-1, 0, 1, 2, 3]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! list {
+    () => {
+        $crate::nil()
+    };
+    ($($head:expr),+ $(,)?; $tail:expr) => {{
+        let elems = [$($head),+];
+        let mut list = $tail;
+        for elem in ::std::iter::IntoIterator::into_iter(elems).rev() {
+            list = $crate::List::cons(elem, list);
+        }
+        list
+    }};
+    ($($head:expr),+ $(,)?) => {
+        $crate::list![$($head),+; $crate::nil()]
+    };
+}
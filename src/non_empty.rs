@@ -0,0 +1,204 @@
+//! A list which is statically known to be non-empty.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::result;
+
+use {EmptyListError, List, Node, PointerKind, RcKind};
+use pointer::Link;
+
+/// An immutable cons list which is known to contain at least one element.
+///
+/// Unlike those of [`List`](struct.List.html), the accessors of a
+/// `NonEmptyList` cannot fail.
+///
+/// # Examples
+///
+/// ```rust
+/// use nth_cons_list::{cons, nil, List, NonEmptyList};
+///
+/// let list = NonEmptyList::cons(1, cons(2, cons(3, nil())));
+/// assert_eq!(*list.head(), 1);
+/// assert_eq!(list.tail(), cons(2, cons(3, nil())));
+/// assert_eq!(*list.max(), 3);
+///
+/// let list: List<i32> = cons(4, nil());
+/// assert_eq!(*list.try_into_non_empty().unwrap().last(), 4);
+/// ```
+pub struct NonEmptyList<A, P: PointerKind = RcKind> {
+    node: Link<Node<A, P>, P>
+}
+
+impl<A, P: PointerKind> Clone for NonEmptyList<A, P> {
+    /// Clones the list by cloning a reference counted pointer to
+    /// the list's contents; this operation is very cheap.
+    fn clone(&self) -> Self {
+        NonEmptyList { node: self.node.clone() }
+    }
+}
+
+impl<A, P: PointerKind> NonEmptyList<A, P> {
+    /// Prepends the specified element at the head of the specified list.
+    pub fn cons(head: A, tail: List<A, P>) -> NonEmptyList<A, P> {
+        let len = tail.len() + 1;
+        NonEmptyList { node: Link::new(Node { head, tail, len }) }
+    }
+
+    /// Returns the first element of the list.
+    pub fn head(&self) -> &A {
+        &self.node.head
+    }
+
+    /// Returns a list containing all elements except the first.
+    pub fn tail(&self) -> List<A, P> {
+        self.node.tail.clone()
+    }
+
+    /// Returns the length of this list, which is never zero.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.node.len
+    }
+
+    /// Returns the last element of the list.
+    pub fn last(&self) -> &A {
+        self.node.tail.last().unwrap_or(&self.node.head)
+    }
+
+    /// Returns the greatest element of the list; if several elements are
+    /// equally greatest, the last of them is returned.
+    pub fn max(&self) -> &A where A: Ord {
+        self.node.tail.iter().fold(&self.node.head, |max, elem| if elem >= max { elem } else { max })
+    }
+
+    /// Returns the least element of the list; if several elements are
+    /// equally least, the first of them is returned.
+    pub fn min(&self) -> &A where A: Ord {
+        self.node.tail.iter().fold(&self.node.head, |min, elem| if elem < min { elem } else { min })
+    }
+
+    /// Reduces the elements of this list from first to last using the
+    /// specified function.
+    pub fn reduce<F: FnMut(A, &A) -> A>(&self, f: F) -> A where A: Clone {
+        self.node.tail.fold_left(self.node.head.clone(), f)
+    }
+
+    /// Returns a [`List`](struct.List.html) containing the elements of
+    /// this list, which shares its nodes.
+    pub fn to_list(&self) -> List<A, P> {
+        List { node: Some(self.node.clone()) }
+    }
+
+    /// Converts this list into a [`List`](struct.List.html) containing
+    /// its elements.
+    pub fn into_list(self) -> List<A, P> {
+        List { node: Some(self.node) }
+    }
+}
+
+impl<A, P: PointerKind> List<A, P> {
+    /// Converts this list into a [`NonEmptyList`](struct.NonEmptyList.html),
+    /// or returns an error if this list is empty.
+    pub fn try_into_non_empty(mut self) -> result::Result<NonEmptyList<A, P>, EmptyListError> {
+        match self.node.take() {
+            Some(node) => Ok(NonEmptyList { node }),
+            None => Err(EmptyListError),
+        }
+    }
+}
+
+impl<A, P: PointerKind> TryFrom<List<A, P>> for NonEmptyList<A, P> {
+    type Error = EmptyListError;
+
+    fn try_from(list: List<A, P>) -> result::Result<Self, Self::Error> {
+        list.try_into_non_empty()
+    }
+}
+
+impl<A, P: PointerKind> From<NonEmptyList<A, P>> for List<A, P> {
+    fn from(list: NonEmptyList<A, P>) -> Self {
+        list.into_list()
+    }
+}
+
+impl<A: Display, P: PointerKind> Display for NonEmptyList<A, P> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.to_list(), f)
+    }
+}
+
+impl<A: Debug, P: PointerKind> Debug for NonEmptyList<A, P> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.to_list(), f)
+    }
+}
+
+impl<A: PartialEq, P: PointerKind> PartialEq for NonEmptyList<A, P> {
+    fn eq(&self, other: &NonEmptyList<A, P>) -> bool {
+        self.to_list() == other.to_list()
+    }
+}
+
+impl<A: Eq, P: PointerKind> Eq for NonEmptyList<A, P> {}
+
+impl<A: Hash, P: PointerKind> Hash for NonEmptyList<A, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_list().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use {nil, ArcList};
+
+    #[test]
+    fn test_cons() {
+        let list = NonEmptyList::cons(1, list![2, 3]);
+        assert_eq!(*list.head(), 1);
+        assert_eq!(list.tail(), list![2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.to_list(), list![1, 2, 3]);
+        assert_eq!(format!("{}", list), "1 :: 2 :: 3 :: Nil");
+        assert_eq!(format!("{:?}", list), "1 :: 2 :: 3 :: Nil");
+
+        let single = NonEmptyList::cons(1, nil());
+        assert!(single.tail().is_empty());
+        assert_eq!(single.len(), 1);
+    }
+
+    #[test]
+    fn test_try_into_non_empty() {
+        let list = list![1, 2];
+        let non_empty = list.clone().try_into_non_empty().unwrap();
+        assert_eq!(non_empty, NonEmptyList::cons(1, list![2]));
+        assert_eq!(List::from(non_empty.clone()), list);
+        assert_eq!(non_empty.into_list(), list);
+
+        assert_eq!(nil::<i32>().try_into_non_empty(), Err(EmptyListError));
+        assert_eq!(NonEmptyList::try_from(list![1]), Ok(NonEmptyList::cons(1, nil())));
+        assert_eq!(NonEmptyList::try_from(ArcList::<i32>::nil()), Err(EmptyListError));
+    }
+
+    #[test]
+    fn test_reductions() {
+        let list = NonEmptyList::cons(3, list![1, 4, 1, 5]);
+        assert_eq!(*list.last(), 5);
+        assert_eq!(*list.max(), 5);
+        assert_eq!(*list.min(), 1);
+        assert_eq!(list.reduce(|acc, i| acc * i), 60);
+
+        let single = NonEmptyList::cons(7, nil());
+        assert_eq!(*single.last(), 7);
+        assert_eq!(*single.max(), 7);
+        assert_eq!(*single.min(), 7);
+        assert_eq!(single.reduce(|acc, i| acc * i), 7);
+
+        // Ties are resolved as they are by `Iterator::max` and `Iterator::min`
+        let equal = NonEmptyList::cons(1, list![1]);
+        assert!(ptr::eq(equal.max(), equal.last()));
+        assert!(ptr::eq(equal.min(), equal.head()));
+    }
+}
@@ -0,0 +1,211 @@
+//! Kinds of reference-counted pointer used to share the nodes of a list.
+
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::result;
+use std::sync::Arc;
+
+/// A kind of reference-counted pointer which a [`List`](struct.List.html)
+/// uses to share its nodes.
+///
+/// This trait is sealed and cannot be implemented outside of this crate;
+/// it is implemented by [`RcKind`](enum.RcKind.html) and
+/// [`ArcKind`](enum.ArcKind.html).
+pub trait PointerKind: private::Sealed {
+    /// The pointer type for this kind.
+    type Pointer<T>: Deref<Target=T> + Clone;
+
+    /// Allocates a new pointer to the specified value.
+    fn new<T>(value: T) -> Self::Pointer<T>;
+
+    /// Consumes the pointer, returning a raw pointer to the inner value
+    /// without changing its reference count.
+    fn into_raw<T>(ptr: Self::Pointer<T>) -> *const T;
+
+    /// Reconstructs a pointer from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have been returned by `into_raw` for this kind, and
+    /// each call must be balanced by one of those references.
+    unsafe fn from_raw<T>(raw: *const T) -> Self::Pointer<T>;
+
+    /// Returns the inner value if the pointer is the only reference to it;
+    /// otherwise, returns the pointer unchanged.
+    fn try_unwrap<T>(ptr: Self::Pointer<T>) -> result::Result<T, Self::Pointer<T>>;
+
+    /// Returns a mutable reference to the inner value if the pointer is
+    /// the only reference to it.
+    fn get_mut<T>(ptr: &mut Self::Pointer<T>) -> Option<&mut T>;
+
+    /// Returns a mutable reference to the inner value, first replacing
+    /// the pointer with a pointer to a clone of the value if it is not
+    /// the only reference to it.
+    fn make_mut<T: Clone>(ptr: &mut Self::Pointer<T>) -> &mut T;
+}
+
+/// The [`PointerKind`](trait.PointerKind.html) for `std::rc::Rc`, used by
+/// [`List`](struct.List.html) by default.
+pub enum RcKind {}
+
+/// The [`PointerKind`](trait.PointerKind.html) for `std::sync::Arc`, used
+/// by [`ArcList`](type.ArcList.html).
+pub enum ArcKind {}
+
+impl PointerKind for RcKind {
+    type Pointer<T> = Rc<T>;
+
+    fn new<T>(value: T) -> Rc<T> {
+        Rc::new(value)
+    }
+
+    fn into_raw<T>(ptr: Rc<T>) -> *const T {
+        Rc::into_raw(ptr)
+    }
+
+    unsafe fn from_raw<T>(raw: *const T) -> Rc<T> {
+        Rc::from_raw(raw)
+    }
+
+    fn try_unwrap<T>(ptr: Rc<T>) -> result::Result<T, Rc<T>> {
+        Rc::try_unwrap(ptr)
+    }
+
+    fn get_mut<T>(ptr: &mut Rc<T>) -> Option<&mut T> {
+        Rc::get_mut(ptr)
+    }
+
+    fn make_mut<T: Clone>(ptr: &mut Rc<T>) -> &mut T {
+        Rc::make_mut(ptr)
+    }
+}
+
+impl PointerKind for ArcKind {
+    type Pointer<T> = Arc<T>;
+
+    fn new<T>(value: T) -> Arc<T> {
+        Arc::new(value)
+    }
+
+    fn into_raw<T>(ptr: Arc<T>) -> *const T {
+        Arc::into_raw(ptr)
+    }
+
+    unsafe fn from_raw<T>(raw: *const T) -> Arc<T> {
+        Arc::from_raw(raw)
+    }
+
+    fn try_unwrap<T>(ptr: Arc<T>) -> result::Result<T, Arc<T>> {
+        Arc::try_unwrap(ptr)
+    }
+
+    fn get_mut<T>(ptr: &mut Arc<T>) -> Option<&mut T> {
+        Arc::get_mut(ptr)
+    }
+
+    fn make_mut<T: Clone>(ptr: &mut Arc<T>) -> &mut T {
+        Arc::make_mut(ptr)
+    }
+}
+
+/// A pointer of kind `P` to a `T`, as stored in the nodes of a list.
+///
+/// The pointer is kept in its raw form rather than as a `P::Pointer<T>`,
+/// since a type containing a projection is invariant in `T`; this keeps
+/// `List<A>` covariant in `A`, as `Rc<T>` and `Arc<T>` are in `T`.
+pub struct Link<T, P: PointerKind> {
+    raw: NonNull<T>,
+    marker: PhantomData<(T, P)>,
+}
+
+impl<T, P: PointerKind> Link<T, P> {
+    /// Allocates a new pointer to the specified value.
+    pub fn new(value: T) -> Link<T, P> {
+        Link::from_pointer(P::new(value))
+    }
+
+    fn from_pointer(ptr: P::Pointer<T>) -> Link<T, P> {
+        // `into_raw` never returns a null pointer
+        let raw = unsafe { NonNull::new_unchecked(P::into_raw(ptr) as *mut T) };
+        Link { raw, marker: PhantomData }
+    }
+
+    fn into_pointer(self) -> P::Pointer<T> {
+        // The reference owned by this link is handed over to the pointer
+        let ptr = unsafe { P::from_raw(self.raw.as_ptr()) };
+        mem::forget(self);
+        ptr
+    }
+
+    /// Returns the pointer without taking over the reference owned by
+    /// this link, so dropping it does not change the reference count.
+    fn as_pointer(&self) -> ManuallyDrop<P::Pointer<T>> {
+        ManuallyDrop::new(unsafe { P::from_raw(self.raw.as_ptr()) })
+    }
+
+    /// Returns the inner value if the pointer is the only reference to it;
+    /// otherwise, returns the pointer unchanged.
+    pub fn try_unwrap(this: Link<T, P>) -> result::Result<T, Link<T, P>> {
+        P::try_unwrap(this.into_pointer()).map_err(Link::from_pointer)
+    }
+
+    /// Returns a mutable reference to the inner value if the pointer is
+    /// the only reference to it.
+    pub fn get_mut(this: &mut Link<T, P>) -> Option<&mut T> {
+        if P::get_mut(&mut this.as_pointer()).is_some() {
+            Some(unsafe { this.raw.as_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the inner value, first replacing
+    /// the pointer with a pointer to a clone of the value if it is not
+    /// the only reference to it.
+    pub fn make_mut(this: &mut Link<T, P>) -> &mut T where T: Clone {
+        let mut ptr = this.as_pointer();
+        P::make_mut(&mut ptr);
+        // The old reference was either kept or released by `make_mut`
+        mem::forget(mem::replace(this, Link::from_pointer(ManuallyDrop::into_inner(ptr))));
+        unsafe { this.raw.as_mut() }
+    }
+
+    /// Tests whether two pointers point to the same allocation.
+    pub fn ptr_eq(a: &Link<T, P>, b: &Link<T, P>) -> bool {
+        a.raw == b.raw
+    }
+}
+
+impl<T, P: PointerKind> Deref for Link<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+impl<T, P: PointerKind> Clone for Link<T, P> {
+    fn clone(&self) -> Link<T, P> {
+        Link::from_pointer(P::Pointer::clone(&self.as_pointer()))
+    }
+}
+
+impl<T, P: PointerKind> Drop for Link<T, P> {
+    fn drop(&mut self) {
+        drop(unsafe { P::from_raw(self.raw.as_ptr()) });
+    }
+}
+
+// A link is only as thread-safe as the pointer it stands for
+unsafe impl<T: Send + Sync> Send for Link<T, ArcKind> {}
+unsafe impl<T: Send + Sync> Sync for Link<T, ArcKind> {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RcKind {}
+    impl Sealed for super::ArcKind {}
+}
@@ -0,0 +1,125 @@
+//! `Serialize` and `Deserialize` implementations for lists and for
+//! `SharedLists`, enabled by the `serde` feature.
+
+use std::fmt::{self, Formatter};
+use std::iter;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use {List, PointerKind, SharedLists};
+
+impl<A: Serialize, P: PointerKind> Serialize for List<A, P> {
+    /// Serializes the list as a sequence of its elements.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+impl<'de, A: Deserialize<'de>, P: PointerKind> Deserialize<'de> for List<A, P> {
+    /// Deserializes the list from a sequence of its elements.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+struct ListVisitor<A, P>(PhantomData<(A, P)>);
+
+impl<'de, A: Deserialize<'de>, P: PointerKind> Visitor<'de> for ListVisitor<A, P> {
+    type Value = List<A, P>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        // The list is built directly from the sequence, stopping at the
+        // first error, which is reported once the list has been built
+        let mut error = None;
+        let elems = iter::from_fn(|| match seq.next_element() {
+            Ok(elem) => elem,
+            Err(e) => {
+                error = Some(e);
+                None
+            }
+        });
+        let list = List::prepend_iter(elems, List::nil());
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(list),
+        }
+    }
+}
+
+impl<A: Serialize> Serialize for SharedLists<A> {
+    /// Serializes the nodes and the roots as a pair of sequences.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.nodes(), self.roots()).serialize(serializer)
+    }
+}
+
+impl<'de, A: Deserialize<'de>> Deserialize<'de> for SharedLists<A> {
+    /// Deserializes the nodes and the roots from a pair of sequences,
+    /// failing if any of them refers to an invalid node.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (nodes, roots) = Deserialize::deserialize(deserializer)?;
+        SharedLists::from_parts(nodes, roots).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use {cons, nil, ArcList, List, SharedLists};
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(serde_json::to_string(&cons(1, cons(2, cons(3, nil())))).unwrap(), "[1,2,3]");
+        assert_eq!(serde_json::to_string(&nil::<i32>()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let list: List<i32> = serde_json::from_str("[1,2,3]").unwrap();
+        assert_eq!(list, cons(1, cons(2, cons(3, nil()))));
+        assert_eq!(list.tail().len(), 2);
+
+        let empty: List<i32> = serde_json::from_str("[]").unwrap();
+        assert!(empty.is_empty());
+
+        assert!(serde_json::from_str::<List<i32>>("[1,\"2\",3]").is_err());
+        assert!(serde_json::from_str::<List<i32>>("{}").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let list = list![list!["a".to_string()], list![], list!["b".to_string(), "c".to_string()]];
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"[["a"],[],["b","c"]]"#);
+        assert_eq!(serde_json::from_str::<List<List<String>>>(&json).unwrap(), list);
+
+        let arc: ArcList<u64> = (0..1000).collect();
+        let json = serde_json::to_string(&arc).unwrap();
+        assert_eq!(serde_json::from_str::<ArcList<u64>>(&json).unwrap(), arc);
+    }
+
+    #[test]
+    fn test_shared_lists() {
+        let tail = list![3, 4];
+        let a = list![1; tail.clone()];
+        let b = list![2; tail.clone()];
+
+        let json = serde_json::to_string(&SharedLists::from_lists(&[a.clone(), b.clone(), nil()])).unwrap();
+        assert_eq!(json, "[[[4,null],[3,0],[1,1],[2,1]],[2,3,null]]");
+
+        let shared: SharedLists<i32> = serde_json::from_str(&json).unwrap();
+        let lists: Vec<List<i32>> = shared.into_lists();
+        assert_eq!(lists, vec![a, b, nil()]);
+
+        assert!(serde_json::from_str::<SharedLists<i32>>("[[[4,0]],[0]]").is_err());
+        assert!(serde_json::from_str::<SharedLists<i32>>("[[[4,null]],[1]]").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! A representation of a collection of lists which preserves the nodes
+//! shared between them.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::result;
+
+use {List, Node, PointerKind};
+
+/// A collection of lists in which every node is stored once, no matter
+/// how many of the lists share it.
+///
+/// Each node is stored as its element and the index of the node
+/// following it, if any; a node only ever refers to a node which
+/// precedes it. Each list is stored as the index of its first node, or
+/// `None` if it is empty.
+///
+/// This is intended for serializing lists which share their tails
+/// without duplicating the shared nodes; with the `serde` feature
+/// enabled, it implements `Serialize` and `Deserialize`.
+///
+/// # Examples
+///
+/// ```rust
+/// use nth_cons_list::{cons, nil, List, SharedLists};
+///
+/// let tail = cons(3, cons(4, nil()));
+/// let a = cons(1, tail.clone());
+/// let b = cons(2, tail.clone());
+///
+/// let shared = SharedLists::from_lists(&[a.clone(), b.clone()]);
+/// assert_eq!(shared.nodes().len(), 4);
+///
+/// let lists: Vec<List<i32>> = shared.into_lists();
+/// assert_eq!(lists, vec![a, b]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedLists<A> {
+    nodes: Vec<(A, Option<usize>)>,
+    roots: Vec<Option<usize>>,
+}
+
+impl<A> SharedLists<A> {
+    /// Creates a `SharedLists` containing the specified lists, in order.
+    ///
+    /// Nodes are considered shared if they are the same node, rather
+    /// than if they contain equal elements.
+    pub fn from_lists<'a, P, I>(lists: I) -> SharedLists<A>
+        where A: 'a + Clone, P: 'a + PointerKind, I: IntoIterator<Item=&'a List<A, P>> {
+        let mut nodes = Vec::new();
+        let mut roots = Vec::new();
+        let mut indices = HashMap::new();
+        let mut pending = Vec::new();
+
+        for list in lists {
+            // Collect the nodes of the list up to the first one which
+            // has already been stored
+            let mut tail = None;
+            let mut rest = list;
+            while let Some(ref ptr) = rest.node {
+                let node: &Node<A, P> = ptr;
+                if let Some(&index) = indices.get(&(node as *const Node<A, P>)) {
+                    tail = Some(index);
+                    break;
+                }
+                pending.push(node);
+                rest = &node.tail;
+            }
+
+            // Store them last to first, so that each refers to one
+            // which precedes it
+            while let Some(node) = pending.pop() {
+                indices.insert(node as *const Node<A, P>, nodes.len());
+                nodes.push((node.head.clone(), tail));
+                tail = Some(nodes.len() - 1);
+            }
+            roots.push(tail);
+        }
+
+        SharedLists { nodes, roots }
+    }
+
+    /// Creates a `SharedLists` from its nodes and the indices of the
+    /// first nodes of its lists.
+    ///
+    /// Returns an error if a node refers to a node which does not precede
+    /// it, or if a list refers to a node which does not exist.
+    pub fn from_parts(nodes: Vec<(A, Option<usize>)>, roots: Vec<Option<usize>>)
+                      -> result::Result<SharedLists<A>, InvalidNodeIndex> {
+        for (i, &(_, tail)) in nodes.iter().enumerate() {
+            match tail {
+                Some(index) if index >= i => return Err(InvalidNodeIndex { index }),
+                _ => {}
+            }
+        }
+        for &root in &roots {
+            match root {
+                Some(index) if index >= nodes.len() => return Err(InvalidNodeIndex { index }),
+                _ => {}
+            }
+        }
+        Ok(SharedLists { nodes, roots })
+    }
+
+    /// Returns the nodes, each as its element and the index of the node
+    /// following it, if any.
+    pub fn nodes(&self) -> &[(A, Option<usize>)] {
+        &self.nodes
+    }
+
+    /// Returns the index of the first node of each list, or `None` for
+    /// each empty list.
+    pub fn roots(&self) -> &[Option<usize>] {
+        &self.roots
+    }
+
+    /// Reconstructs the lists, sharing nodes between them as they were
+    /// shared by the lists this was created from.
+    pub fn into_lists<P: PointerKind>(self) -> Vec<List<A, P>> {
+        let mut built: Vec<List<A, P>> = Vec::with_capacity(self.nodes.len());
+        for (head, tail) in self.nodes {
+            let tail = tail.map_or_else(List::nil, |index| built[index].clone());
+            built.push(List::cons(head, tail));
+        }
+
+        self.roots.iter()
+            .map(|root| root.map_or_else(List::nil, |index| built[index].clone()))
+            .collect()
+    }
+}
+
+/// An error indicating that a [`SharedLists`](struct.SharedLists.html)
+/// would refer to a node at an invalid index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidNodeIndex {
+    index: usize,
+}
+
+impl InvalidNodeIndex {
+    /// Returns the invalid index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Display for InvalidNodeIndex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid node index: {}", self.index)
+    }
+}
+
+impl Error for InvalidNodeIndex {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {cons, nil, ArcList};
+
+    fn node_ptr<A>(list: &List<A>) -> *const Node<A, ::RcKind> {
+        list.node.as_ref().map_or(::std::ptr::null(), |ptr| &**ptr as *const _)
+    }
+
+    #[test]
+    fn test_from_lists() {
+        let tail = list![3, 4];
+        let a = list![1; tail.clone()];
+        let b = list![2; tail.clone()];
+        let c = list![2, 3, 4];
+
+        let shared = SharedLists::from_lists(vec![&a, &b, &tail, &c, &nil()]);
+        assert_eq!(shared.nodes(), &[(4, None), (3, Some(0)), (1, Some(1)), (2, Some(1)),
+                                     (4, None), (3, Some(4)), (2, Some(5))]);
+        assert_eq!(shared.roots(), &[Some(2), Some(3), Some(1), Some(6), None]);
+
+        let empty = SharedLists::<i32>::from_lists(Vec::<&List<i32>>::new());
+        assert!(empty.nodes().is_empty());
+        assert!(empty.roots().is_empty());
+    }
+
+    #[test]
+    fn test_into_lists() {
+        let tail = list![3, 4];
+        let a = list![1; tail.clone()];
+        let b = list![2; tail.clone()];
+
+        let lists: Vec<List<i32>> = SharedLists::from_lists(&[a.clone(), b.clone(), tail.clone()]).into_lists();
+        assert_eq!(lists, vec![a, b, tail]);
+        assert_eq!(node_ptr(&lists[0].tail()), node_ptr(&lists[2]));
+        assert_eq!(node_ptr(&lists[1].tail()), node_ptr(&lists[2]));
+        assert_eq!(lists[0].len(), 3);
+
+        let arc: Vec<ArcList<i32>> = SharedLists::from_lists(&[cons(1, nil()), nil()]).into_lists();
+        assert_eq!(arc, vec![ArcList::cons(1, ArcList::nil()), ArcList::nil()]);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let shared = SharedLists::from_parts(vec![(1, None), (2, Some(0))], vec![Some(1), None]).unwrap();
+        assert_eq!(shared.into_lists::<::RcKind>(), vec![list![2, 1], nil()]);
+
+        assert_eq!(SharedLists::from_parts(vec![(1, Some(0))], vec![]).unwrap_err().index(), 0);
+        assert_eq!(SharedLists::from_parts(vec![(1, Some(1)), (2, None)], vec![]).unwrap_err().index(), 1);
+        assert_eq!(SharedLists::from_parts(vec![(1, None)], vec![Some(1)]).unwrap_err().index(), 1);
+    }
+}